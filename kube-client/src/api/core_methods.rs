@@ -1,10 +1,16 @@
 use either::Either;
-use futures::Stream;
+use futures::{Stream, TryStreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 use crate::{api::Api, Error, Result};
-use kube_core::{object::ObjectList, params::*, response::Status, ErrorResponse, WatchEvent};
+use kube_core::{
+    metadata::PartialObjectMeta,
+    object::ObjectList,
+    params::*,
+    response::{Created, DeleteOutcome, Ensured, Status},
+    ErrorResponse, Resource, WatchEvent,
+};
 
 /// PUSH/PUT/POST/GET abstractions
 impl<K> Api<K>
@@ -32,6 +38,38 @@ where
     pub async fn get(&self, name: &str) -> Result<K> {
         let mut req = self.request.get(name).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("get");
+        self.override_accept(&mut req);
+        self.client.request::<K>(req).await
+    }
+
+    /// [Get](`Api::get`) a named resource from the apiserver watch cache
+    ///
+    /// This sets `resourceVersion=0`, which lets the apiserver serve the object from its cache
+    /// rather than performing a quorum read against etcd. This is cheaper, which matters for hot read paths.
+    ///
+    /// **Note**: the returned object may be stale, and may even be older than an object previously
+    /// returned by [`Api::get`]. Only use this where eventual consistency is acceptable,
+    /// and never as the basis for a read-modify-write without a `resourceVersion` precondition.
+    ///
+    /// ```no_run
+    /// use kube::{Api, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let p: Pod = pods.get_cached("blog").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Like [`Api::get`], this returns [`Error`] if the object does not exist in the cache.
+    pub async fn get_cached(&self, name: &str) -> Result<K> {
+        let mut req = self.request.get_cached(name).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("get_cached");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
@@ -60,6 +98,29 @@ where
         }
     }
 
+    /// Get only the metadata for a named resource as [`PartialObjectMeta`]
+    ///
+    /// This avoids transferring the full object, which is useful for large objects
+    /// like `ConfigMap` or `Secret` when only the metadata is of interest.
+    ///
+    /// ```no_run
+    /// use kube::{Api, Client, core::PartialObjectMeta};
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let cms: Api<ConfigMap> = Api::namespaced(client, "apps");
+    ///     let meta: PartialObjectMeta = cms.get_metadata("big-config").await?;
+    ///     println!("Labels: {:?}", meta.metadata.labels);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_metadata(&self, name: &str) -> Result<PartialObjectMeta> {
+        let mut req = self.request.get_metadata(name).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("get_metadata");
+        self.client.request::<PartialObjectMeta>(req).await
+    }
+
     /// Get a list of resources
     ///
     /// You get use this to get everything, or a subset matching fields/labels, say:
@@ -81,9 +142,108 @@ where
     pub async fn list(&self, lp: &ListParams) -> Result<ObjectList<K>> {
         let mut req = self.request.list(lp).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("list");
+        self.override_accept(&mut req);
         self.client.request::<ObjectList<K>>(req).await
     }
 
+    /// Stream all resources matching `lp`, one page at a time
+    ///
+    /// Results are fetched in pages of [`ListParams::limit`] objects, following continue tokens until exhausted,
+    /// so that only a single page is held in memory at once. Without a limit, everything is returned in one page.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams, ResourceExt}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::{pin_mut, TryStreamExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::all(client);
+    ///     let pods = pods.list_paged(&ListParams::default().limit(500));
+    ///     pin_mut!(pods);
+    ///     while let Some(p) = pods.try_next().await? {
+    ///         println!("Found Pod: {}", p.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_paged<'a>(&'a self, lp: &ListParams) -> impl Stream<Item = Result<K>> + 'a {
+        futures::stream::try_unfold(Some(lp.clone()), move |lp| async move {
+            let lp = match lp {
+                Some(lp) => lp,
+                None => return Ok(None),
+            };
+            let page = self.list(&lp).await?;
+            let next = match page.metadata.continue_ {
+                // The token pins the resource version of the first page
+                Some(token) if !token.is_empty() => Some(ListParams {
+                    resource_version: None,
+                    resource_version_match: None,
+                    ..lp.continue_token(&token)
+                }),
+                _ => None,
+            };
+            Ok(Some((page.items, next)))
+        })
+        .map_ok(|items| futures::stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Stream all resources matching `lp` on the server and `predicate` on the client
+    ///
+    /// Label and field selectors in `lp` are evaluated by the apiserver, while `predicate` is applied
+    /// to each returned object for filtering the apiserver cannot do, such as on arbitrary spec values.
+    /// Results are fetched in pages like [`Api::list_paged`].
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams, ResourceExt}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::TryStreamExt;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let lp = ListParams::default().labels("app=blog").limit(100);
+    ///     let host_network = |p: &Pod| p.spec.as_ref().and_then(|s| s.host_network).unwrap_or(false);
+    ///     let pods = pods.list_filtered(&lp, host_network).try_collect::<Vec<_>>().await?;
+    ///     for p in pods {
+    ///         println!("Found host network Pod: {}", p.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_filtered<'a, F>(&'a self, lp: &ListParams, predicate: F) -> impl Stream<Item = Result<K>> + 'a
+    where
+        F: Fn(&K) -> bool + 'a,
+    {
+        self.list_paged(lp)
+            .try_filter(move |obj| futures::future::ready(predicate(obj)))
+    }
+
+    /// Get a list of resources that contains only their metadata as [`PartialObjectMeta`]
+    ///
+    /// Similar to [`Api::list`], but the apiserver only returns the metadata of each object.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams, ResourceExt}, Client};
+    /// use k8s_openapi::api::core::v1::Secret;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let secrets: Api<Secret> = Api::namespaced(client, "apps");
+    ///     let lp = ListParams::default().labels("app=blog");
+    ///     for s in secrets.list_metadata(&lp).await? {
+    ///         println!("Found Secret: {}", s.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_metadata(&self, lp: &ListParams) -> Result<ObjectList<PartialObjectMeta>> {
+        let mut req = self.request.list_metadata(lp).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("list_metadata");
+        self.client.request::<ObjectList<PartialObjectMeta>>(req).await
+    }
+
     /// Create a resource
     ///
     /// This function requires a type that Serializes to `K`, which can be:
@@ -110,9 +270,48 @@ where
         let bytes = serde_json::to_vec(&data).map_err(Error::SerdeError)?;
         let mut req = self.request.create(pp, bytes).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("create");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
+    /// Create a resource and return it along with its assigned identity
+    ///
+    /// This works like [`Api::create`], but wraps the result in a [`Created`] that exposes the
+    /// `name`, `uid`, and `resourceVersion` assigned by the apiserver. This is particularly useful
+    /// when creating objects with `metadata.generateName`.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, PostParams}, Client};
+    /// use k8s_openapi::api::batch::v1::Job;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let jobs: Api<Job> = Api::namespaced(client, "apps");
+    ///     let job: Job = serde_json::from_value(serde_json::json!({
+    ///         "apiVersion": "batch/v1",
+    ///         "kind": "Job",
+    ///         "metadata": { "generateName": "migrate-" },
+    ///         "spec": {
+    ///             "template": {
+    ///                 "spec": {
+    ///                     "containers": [{ "name": "migrate", "image": "alpine:latest" }],
+    ///                     "restartPolicy": "Never",
+    ///                 }
+    ///             }
+    ///         }
+    ///     }))?;
+    ///     let created = jobs.create_with_identity(&PostParams::default(), &job).await?;
+    ///     println!("Created {} with uid {}", created.name(), created.uid());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_with_identity(&self, pp: &PostParams, data: &K) -> Result<Created<K>>
+    where
+        K: Resource + Serialize,
+    {
+        self.create(pp, data).await.map(Created::new)
+    }
+
     /// Delete a named resource
     ///
     /// When you get a `K` via `Left`, your delete has started.
@@ -138,9 +337,41 @@ where
     pub async fn delete(&self, name: &str, dp: &DeleteParams) -> Result<Either<K, Status>> {
         let mut req = self.request.delete(name, dp).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("delete");
+        self.override_accept(&mut req);
         self.client.request_status::<K>(req).await
     }
 
+    /// Delete a named resource and report whether it was removed or is terminating
+    ///
+    /// This classifies the response of [`Api::delete`]: an object with a `deletionTimestamp` is
+    /// [`DeleteOutcome::Terminating`] (e.g. blocked on finalizers or foreground deletion),
+    /// anything else was removed immediately. Callers that get `Terminating` may want to
+    /// keep watching the object until it is actually gone.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, DeleteOutcome, DeleteParams}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     match pods.delete_with_outcome("blog", &DeleteParams::default()).await? {
+    ///         DeleteOutcome::Terminating(pod) => println!("Waiting for finalizers: {:?}", pod.metadata.finalizers),
+    ///         DeleteOutcome::Removed(_) => println!("Deleted blog"),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_with_outcome(&self, name: &str, dp: &DeleteParams) -> Result<DeleteOutcome<K>>
+    where
+        K: Resource,
+    {
+        Ok(match self.delete(name, dp).await? {
+            Either::Left(obj) => DeleteOutcome::from_object(obj),
+            Either::Right(_) => DeleteOutcome::Removed(None),
+        })
+    }
+
     /// Delete a collection of resources
     ///
     /// When you get an `ObjectList<K>` via `Left`, your delete has started.
@@ -178,6 +409,7 @@ where
             .delete_collection(dp, lp)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("delete_collection");
+        self.override_accept(&mut req);
         self.client.request_status::<ObjectList<K>>(req).await
     }
 
@@ -221,9 +453,57 @@ where
     ) -> Result<K> {
         let mut req = self.request.patch(name, pp, patch).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("patch");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
+    /// Patch a named resource only if the desired fields differ from the live object
+    ///
+    /// This fetches the object and compares the fields present in `desired` against it,
+    /// following the semantics of a JSON merge patch: nested objects are compared field by field,
+    /// arrays and scalars must be equal, and `null` requires the field to be absent.
+    /// A [`Patch::Merge`] of `desired` is only sent when something differs,
+    /// which avoids no-op writes in reconcile loops.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, PatchParams}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let desired = serde_json::json!({
+    ///         "metadata": {
+    ///             "labels": { "app": "blog" }
+    ///         }
+    ///     });
+    ///     let res = pods.ensure("blog", &PatchParams::default(), &desired).await?;
+    ///     if res.is_patched() {
+    ///         println!("Labelled blog");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn ensure<P: Serialize + Debug>(
+        &self,
+        name: &str,
+        pp: &PatchParams,
+        desired: &P,
+    ) -> Result<Ensured<K>>
+    where
+        K: Serialize,
+    {
+        let desired = serde_json::to_value(desired).map_err(Error::SerdeError)?;
+        let live = self.get(name).await?;
+        let live_json = serde_json::to_value(&live).map_err(Error::SerdeError)?;
+        if merge_patch_is_noop(&desired, &live_json) {
+            return Ok(Ensured::Unchanged(live));
+        }
+        self.patch(name, pp, &Patch::Merge(&desired))
+            .await
+            .map(Ensured::Patched)
+    }
+
     /// Replace a resource entirely with a new one
     ///
     /// This is used just like [`Api::create`], but with one additional instruction:
@@ -232,6 +512,11 @@ where
     ///
     /// Thus, to use this function, you need to do a `get` then a `replace` with its result.
     ///
+    /// The `resourceVersion` is sent as part of the object and acts as a precondition:
+    /// if the object has been modified since it was read, the apiserver rejects the write
+    /// and this returns an [`Error::Api`] with code `409` and reason `"Conflict"`.
+    /// Re-read the object and reapply your changes before retrying.
+    ///
     /// ```no_run
     /// use kube::{api::{Api, PostParams, ResourceExt}, Client};
     /// use k8s_openapi::api::batch::v1::Job;
@@ -281,6 +566,7 @@ where
             .replace(name, pp, bytes)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("replace");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
@@ -330,4 +616,286 @@ where
         req.extensions_mut().insert("watch");
         self.client.request_events::<K>(req).await
     }
+
+    /// Watch a single named resource
+    ///
+    /// This is a [`watch`](Api::watch) with a `metadata.name` field selector, so that the apiserver
+    /// only sends events for the object called `name`, rather than for the whole collection.
+    ///
+    /// The same caveats as for [`Api::watch`] apply; consider using a managed [`watch_object`] to deal
+    /// with automatic re-watches and error cases.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, WatchEvent}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::{StreamExt, TryStreamExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let mut stream = pods.watch_one("blog", "0").await?.boxed();
+    ///     while let Some(event) = stream.try_next().await? {
+    ///         if let WatchEvent::Modified(pod) = event {
+    ///             println!("Phase: {:?}", pod.status.and_then(|s| s.phase));
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    /// [`watch_object`]: https://docs.rs/kube_runtime/*/kube_runtime/watcher/fn.watch_object.html
+    pub async fn watch_one(&self, name: &str, version: &str) -> Result<impl Stream<Item = Result<WatchEvent<K>>>> {
+        let lp = ListParams::default().fields_eq("metadata.name", name);
+        self.watch(&lp, version).await
+    }
+
+    /// Watch a list of resources for changes made after this call, skipping existing objects
+    ///
+    /// This fetches the current resourceVersion of the collection through a minimal metadata list,
+    /// and then [watches](Api::watch) from that version. No `Added` events are replayed for objects
+    /// that already existed, so only changes that happen from now on are seen.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams, WatchEvent}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::{StreamExt, TryStreamExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let mut stream = pods.watch_from_now(&ListParams::default()).await?.boxed();
+    ///     while let Some(event) = stream.try_next().await? {
+    ///         if let WatchEvent::Added(pod) = event {
+    ///             println!("New pod: {:?}", pod.metadata.name);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn watch_from_now(&self, lp: &ListParams) -> Result<impl Stream<Item = Result<WatchEvent<K>>>> {
+        // The watch starts at the latest version, rather than at any version or paging set for the initial list
+        let watch_params = ListParams {
+            continue_token: None,
+            resource_version: None,
+            resource_version_match: None,
+            ..lp.clone()
+        };
+        let list = self.list_metadata(&watch_params.clone().limit(1)).await?;
+        let version = list.metadata.resource_version.unwrap_or_default();
+        self.watch(&watch_params, &version).await
+    }
+
+    /// Watch the metadata of a list of resources
+    ///
+    /// This works like [`Api::watch`], but the events only contain [`PartialObjectMeta`].
+    ///
+    /// Consider using a managed [`metadata_watcher`] to deal with automatic re-watches and error cases.
+    ///
+    /// [`metadata_watcher`]: https://docs.rs/kube_runtime/*/kube_runtime/watcher/fn.metadata_watcher.html
+    pub async fn watch_metadata(
+        &self,
+        lp: &ListParams,
+        version: &str,
+    ) -> Result<impl Stream<Item = Result<WatchEvent<PartialObjectMeta>>>> {
+        let mut req = self
+            .request
+            .watch_metadata(lp, version)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("watch_metadata");
+        self.client.request_events::<PartialObjectMeta>(req).await
+    }
+}
+
+/// Whether applying `patch` as a JSON merge patch would leave `target` unchanged
+fn merge_patch_is_noop(patch: &serde_json::Value, target: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match patch {
+        Value::Object(fields) => {
+            fields.iter().all(|(key, value)| match (value, target.get(key)) {
+                (Value::Null, None | Some(Value::Null)) => true,
+                (Value::Null, Some(_)) | (_, None) => false,
+                (value, Some(current)) => merge_patch_is_noop(value, current),
+            }) && target.is_object()
+        }
+        _ => patch == target,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::merge_patch_is_noop;
+    use crate::{
+        api::{Api, ListParams, PostParams},
+        Client, Error,
+    };
+    use futures::{pin_mut, TryStreamExt};
+    use http::{Request, Response};
+    use hyper::Body;
+    use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+    use serde_json::json;
+    use tower_test::mock;
+
+    #[tokio::test]
+    async fn list_paged_drops_resource_version_when_continuing() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&limit=1&resourceVersion=0"));
+            let page = json!({ "metadata": { "continue": "page2" }, "items": [{ "metadata": { "name": "a" } }] });
+            send.send_response(Response::builder().body(Body::from(page.to_string())).unwrap());
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&limit=1&continue=page2"));
+            let page = json!({ "metadata": {}, "items": [{ "metadata": { "name": "b" } }] });
+            send.send_response(Response::builder().body(Body::from(page.to_string())).unwrap());
+        });
+
+        let api: Api<ConfigMap> = Api::namespaced(Client::new(mock_service, "default"), "default");
+        let names = api
+            .list_paged(&ListParams::default().limit(1).at("0"))
+            .map_ok(|cm| cm.metadata.name.unwrap())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(names, ["a", "b"]);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn watch_from_now_drops_resource_version() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&limit=1"));
+            let list = json!({ "metadata": { "resourceVersion": "5" }, "items": [] });
+            send.send_response(Response::builder().body(Body::from(list.to_string())).unwrap());
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(
+                request.uri().query(),
+                Some("&watch=true&resourceVersion=5&timeoutSeconds=290&allowWatchBookmarks=true")
+            );
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+        });
+
+        let api: Api<ConfigMap> = Api::namespaced(Client::new(mock_service, "default"), "default");
+        let events = api
+            .watch_from_now(&ListParams::default().at("0"))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn replace_with_stale_resource_version_conflicts() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::PUT);
+            assert_eq!(request.uri().path(), "/api/v1/namespaces/default/configmaps/cm");
+            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+            let sent: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(sent["metadata"]["resourceVersion"], "1");
+            let status = json!({
+                "status": "Failure",
+                "message": "Operation cannot be fulfilled on configmaps \"cm\": the object has been modified",
+                "reason": "Conflict",
+                "code": 409,
+            });
+            send.send_response(
+                Response::builder()
+                    .status(409)
+                    .body(Body::from(status.to_string()))
+                    .unwrap(),
+            );
+        });
+
+        let api: Api<ConfigMap> = Api::namespaced(Client::new(mock_service, "default"), "default");
+        let cm: ConfigMap = serde_json::from_value(json!({
+            "metadata": { "name": "cm", "resourceVersion": "1" },
+        }))
+        .unwrap();
+        let res = api.replace("cm", &PostParams::default(), &cm).await;
+        assert!(matches!(res, Err(Error::Api(err)) if err.code == 409 && err.reason == "Conflict"));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn watch_all_namespaces_omits_namespace() {
+        use futures::TryStreamExt;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/pods");
+            assert!(request.uri().query().unwrap().contains("watch=true"));
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+        });
+
+        let api: Api<Pod> = Api::all(Client::new(mock_service, "default"));
+        let events = api.watch(&ListParams::default(), "0").await.unwrap();
+        assert!(events.try_collect::<Vec<_>>().await.unwrap().is_empty());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn watch_one_selects_by_name() {
+        use futures::TryStreamExt;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/namespaces/default/pods");
+            let query = request.uri().query().unwrap();
+            assert!(query.contains("fieldSelector=metadata.name%3Dblog"));
+            assert!(query.contains("watch=true&resourceVersion=5"));
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+        });
+
+        let api: Api<Pod> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let events = api.watch_one("blog", "5").await.unwrap();
+        assert!(events.try_collect::<Vec<_>>().await.unwrap().is_empty());
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn merge_patch_noop_detection() {
+        let live = json!({
+            "metadata": { "name": "blog", "labels": { "app": "blog", "tier": "web" } },
+            "spec": { "containers": [{ "name": "blog", "image": "clux/blog:0.1.0" }] }
+        });
+        assert!(merge_patch_is_noop(&json!({}), &live));
+        assert!(merge_patch_is_noop(
+            &json!({ "metadata": { "labels": { "app": "blog" } } }),
+            &live
+        ));
+        assert!(merge_patch_is_noop(
+            &json!({ "metadata": { "annotations": null } }),
+            &live
+        ));
+
+        assert!(!merge_patch_is_noop(
+            &json!({ "metadata": { "labels": { "app": "api" } } }),
+            &live
+        ));
+        assert!(!merge_patch_is_noop(
+            &json!({ "metadata": { "labels": { "tier": null } } }),
+            &live
+        ));
+        assert!(!merge_patch_is_noop(
+            &json!({ "metadata": { "annotations": { "a": "b" } } }),
+            &live
+        ));
+        // arrays are replaced wholesale by merge patches, so partial elements differ
+        assert!(!merge_patch_is_noop(
+            &json!({ "spec": { "containers": [{ "name": "blog" }] } }),
+            &live
+        ));
+    }
 }
@@ -27,16 +27,17 @@ pub(crate) use kube_core::params;
 pub use kube_core::{
     dynamic::{ApiResource, DynamicObject},
     gvk::{GroupVersionKind, GroupVersionResource},
-    metadata::{ListMeta, ObjectMeta, TypeMeta},
+    metadata::{ListMeta, ObjectMeta, PartialObjectMeta, TypeMeta},
     object::{NotUsed, Object, ObjectList},
     request::Request,
+    response::{Created, DeleteOutcome, Ensured},
     watch::WatchEvent,
     Resource, ResourceExt,
 };
 use kube_core::{DynamicResourceScope, NamespaceResourceScope};
 pub use params::{
     DeleteParams, ListParams, Patch, PatchParams, PostParams, Preconditions, PropagationPolicy,
-    ValidationDirective,
+    ResourceVersionMatch, ValidationDirective,
 };
 
 use crate::Client;
@@ -53,6 +54,8 @@ pub struct Api<K> {
     /// The client to use (from this library)
     pub(crate) client: Client,
     namespace: Option<String>,
+    /// Overrides the `Accept` header of requests for objects, see [`Api::with_accept`]
+    accept: Option<http::HeaderValue>,
     /// Note: Using `iter::Empty` over `PhantomData`, because we never actually keep any
     /// `K` objects, so `Empty` better models our constraints (in particular, `Empty<K>`
     /// is `Send`, even if `K` may not be).
@@ -72,6 +75,7 @@ impl<K: Resource> Api<K> {
             client,
             request: Request::new(url),
             namespace: None,
+            accept: None,
             _phantom: std::iter::empty(),
         }
     }
@@ -89,6 +93,7 @@ impl<K: Resource> Api<K> {
             client,
             request: Request::new(url),
             namespace: Some(ns.to_string()),
+            accept: None,
             _phantom: std::iter::empty(),
         }
     }
@@ -117,8 +122,37 @@ impl<K: Resource> Api<K> {
     pub fn resource_url(&self) -> &str {
         &self.request.url_path
     }
+
+    /// Override the `Accept` header sent with every request made through this [`Api`]
+    ///
+    /// This is an escape hatch for resources served by aggregated apiservers
+    /// that do not support the content negotiation of the main apiserver.
+    /// Watches and metadata-only requests keep their own `Accept` header, since they rely on it
+    /// to select the format of the response.
+    ///
+    /// ```no_run
+    /// # use kube::{Api, Client, core::DynamicObject, discovery::ApiResource};
+    /// # let client: Client = todo!();
+    /// # let ar: ApiResource = todo!();
+    /// use http::HeaderValue;
+    /// let api: Api<DynamicObject> = Api::all_with(client, &ar)
+    ///     .with_accept(HeaderValue::from_static("application/json"));
+    /// ```
+    #[must_use]
+    pub fn with_accept(mut self, accept: http::HeaderValue) -> Self {
+        self.accept = Some(accept);
+        self
+    }
 }
 
+impl<K> Api<K> {
+    /// Applies the `Accept` override of [`Api::with_accept`] to a request made through this [`Api`]
+    pub(crate) fn override_accept(&self, req: &mut http::Request<Vec<u8>>) {
+        if let Some(accept) = &self.accept {
+            req.headers_mut().insert(http::header::ACCEPT, accept.clone());
+        }
+    }
+}
 
 /// Api constructors for Resource implementors with Default DynamicTypes
 ///
@@ -138,6 +172,10 @@ where
     /// let api: Api<Pod> = Api::all(client);
     /// ```
     ///
+    /// Lists and watches then use the collection path without a namespace segment (e.g. `/api/v1/pods`),
+    /// so they need RBAC permissions granted by a `ClusterRole` and `ClusterRoleBinding`.
+    /// A `Role` only grants access within its own namespace.
+    ///
     /// Cluster scoped resources also use this entrypoint:
     ///
     /// ```no_run
@@ -179,6 +217,7 @@ where
             client,
             request: Request::new(url),
             namespace: Some(ns.to_string()),
+            accept: None,
             _phantom: std::iter::empty(),
         }
     }
@@ -211,6 +250,28 @@ where
         let ns = client.default_ns().to_string();
         Self::namespaced(client, &ns)
     }
+
+    /// Derive an [`Api`] scoped to a given namespace, sharing the same [`Client`]
+    ///
+    /// This is useful with an [`Api::all`] that occasionally needs to work within a single namespace.
+    /// The returned `Api` behaves exactly like one created with [`Api::namespaced`].
+    ///
+    /// ```no_run
+    /// # use kube::{Api, Client};
+    /// # let client: Client = todo!();
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// let all: Api<Pod> = Api::all(client);
+    /// let apps: Api<Pod> = all.within("apps");
+    /// ```
+    pub fn within(&self, ns: &str) -> Self
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+    {
+        Self {
+            accept: self.accept.clone(),
+            ..Self::namespaced(self.client.clone(), ns)
+        }
+    }
 }
 
 impl<K> From<Api<K>> for Client {
@@ -226,12 +287,14 @@ impl<K> Debug for Api<K> {
             request,
             client: _,
             namespace,
+            accept,
             _phantom,
         } = self;
         f.debug_struct("Api")
             .field("request", &request)
             .field("client", &"...")
             .field("namespace", &namespace)
+            .field("accept", &accept)
             .finish()
     }
 }
@@ -256,4 +319,138 @@ mod test {
         let _: Api<corev1::PersistentVolume> = Api::all(client.clone());
         let _: Api<corev1::ConfigMap> = Api::namespaced(client.clone(), "default");
     }
+
+    #[tokio::test]
+    async fn within_derives_namespaced_api() {
+        let (mock_service, _handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default");
+
+        let all: Api<corev1::Pod> = Api::all(client);
+        assert_eq!(all.resource_url(), "/api/v1/pods");
+        let apps = all.within("apps");
+        assert_eq!(apps.resource_url(), "/api/v1/namespaces/apps/pods");
+        assert_eq!(all.resource_url(), "/api/v1/pods");
+    }
+
+    #[tokio::test]
+    async fn watch_all_namespaces_keeps_event_namespaces() {
+        use crate::api::{ListParams, ResourceExt, WatchEvent};
+        use futures::{pin_mut, TryStreamExt};
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/pods");
+            let events = ["apps", "kube-system"]
+                .iter()
+                .map(|ns| {
+                    serde_json::json!({
+                        "type": "ADDED",
+                        "object": {
+                            "apiVersion": "v1",
+                            "kind": "Pod",
+                            "metadata": { "name": "blog", "namespace": ns, "resourceVersion": "1" },
+                        }
+                    })
+                    .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            send.send_response(Response::builder().body(Body::from(events)).unwrap());
+        });
+
+        let pods: Api<corev1::Pod> = Api::all(Client::new(mock_service, "default"));
+        let events = pods
+            .watch(&ListParams::default(), "0")
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let namespaces = events
+            .iter()
+            .map(|event| match event {
+                WatchEvent::Added(pod) => pod.namespace(),
+                _ => panic!("unexpected event {:?}", event),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(namespaces, vec![Some("apps".into()), Some("kube-system".into())]);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn list_filtered_pages_and_filters() {
+        use crate::api::{ListParams, ResourceExt};
+        use futures::{pin_mut, TryStreamExt};
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for (token, names, next) in [("", ["a", "bb"], "page2"), ("page2", ["ccc", "d"], "")] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                let query = request.uri().query().unwrap().to_string();
+                assert!(query.contains("limit=2"));
+                assert_eq!(query.contains("continue="), !token.is_empty());
+                let items = names
+                    .iter()
+                    .map(|name| serde_json::json!({ "metadata": { "name": name } }))
+                    .collect::<Vec<_>>();
+                let list = serde_json::json!({
+                    "apiVersion": "v1",
+                    "kind": "PodList",
+                    "metadata": { "continue": next, "resourceVersion": "1" },
+                    "items": items,
+                });
+                send.send_response(Response::builder().body(Body::from(list.to_string())).unwrap());
+            }
+        });
+
+        let pods: Api<corev1::Pod> = Api::all(Client::new(mock_service, "default"));
+        let lp = ListParams::default().limit(2);
+        let names = pods
+            .list_filtered(&lp, |pod| pod.name_any().len() > 1)
+            .map_ok(|pod| pod.name_any())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["bb", "ccc"]);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn with_accept_overrides_header() {
+        use futures::pin_mut;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(
+                request.headers().get(http::header::ACCEPT).unwrap(),
+                "application/json;v=1"
+            );
+            let pod =
+                serde_json::json!({ "apiVersion": "v1", "kind": "Pod", "metadata": { "name": "blog" } });
+            send.send_response(Response::builder().body(Body::from(pod.to_string())).unwrap());
+
+            // Watches keep their own content negotiation
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers().get(http::header::ACCEPT), None);
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+
+            // The override does not leak into other Apis sharing the client
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers().get(http::header::ACCEPT), None);
+            send.send_response(Response::builder().body(Body::from(pod.to_string())).unwrap());
+        });
+
+        let pods: Api<corev1::Pod> = Api::namespaced(Client::new(mock_service, "default"), "apps")
+            .with_accept(http::HeaderValue::from_static("application/json;v=1"));
+        pods.get("blog").await.unwrap();
+        drop(pods.watch(&Default::default(), "0").await.unwrap());
+        let other: Api<corev1::Pod> = Api::namespaced(pods.into_client(), "apps");
+        other.get("blog").await.unwrap();
+        spawned.await.unwrap();
+    }
 }
@@ -4,7 +4,7 @@ use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
 use crate::{
-    api::{Api, Patch, PatchParams, PostParams},
+    api::{Api, Patch, PatchParams, PostParams, Resource},
     Error, Result,
 };
 
@@ -32,10 +32,36 @@ where
             .get_subresource("scale", name)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("get_scale");
+        self.override_accept(&mut req);
         self.client.request::<Scale>(req).await
     }
 
     /// Update the scale subresource
+    ///
+    /// This works for any resource exposing `/scale`, including custom resources
+    /// that declare the subresource with `#[kube(scale = "...")]`.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, Patch, PatchParams}, Client, CustomResource};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_json::json;
+    /// # use schemars::JsonSchema;
+    /// #[derive(CustomResource, Serialize, Deserialize, Clone, Debug, JsonSchema)]
+    /// #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced, status = "FooStatus")]
+    /// #[kube(scale = r#"{"specReplicasPath":".spec.replicas", "statusReplicasPath":".status.replicas"}"#)]
+    /// struct FooSpec { replicas: i32 }
+    /// #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+    /// struct FooStatus { replicas: i32 }
+    ///
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let foos: Api<Foo> = Api::namespaced(client, "apps");
+    /// let patch = Patch::Merge(json!({ "spec": { "replicas": 3 } }));
+    /// let scale = foos.patch_scale("blog", &PatchParams::default(), &patch).await?;
+    /// println!("{:?}", scale.status);
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn patch_scale<P: serde::Serialize + Debug>(
         &self,
         name: &str,
@@ -47,6 +73,7 @@ where
             .patch_subresource("scale", name, pp, patch)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("patch_scale");
+        self.override_accept(&mut req);
         self.client.request::<Scale>(req).await
     }
 
@@ -57,6 +84,7 @@ where
             .replace_subresource("scale", name, pp, data)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("replace_scale");
+        self.override_accept(&mut req);
         self.client.request::<Scale>(req).await
     }
 }
@@ -73,9 +101,59 @@ where
             .get_subresource(subresource_name, name)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("get_subresource");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
+    /// Create an instance of the subresource
+    ///
+    /// Unlike the other subresource methods, the apiserver rarely returns a `K` here,
+    /// so the type `T` of the response has to be chosen by the caller.
+    /// This is commonly a [`Status`], e.g. when binding a pod to a node from a custom scheduler:
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, PostParams}, core::response::Status, Client};
+    /// use k8s_openapi::api::core::v1::{Binding, ObjectReference, Pod};
+    /// use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let pods: Api<Pod> = Api::namespaced(client, "apps");
+    /// let binding = Binding {
+    ///     metadata: ObjectMeta {
+    ///         name: Some("blog".into()),
+    ///         ..ObjectMeta::default()
+    ///     },
+    ///     target: ObjectReference {
+    ///         api_version: Some("v1".into()),
+    ///         kind: Some("Node".into()),
+    ///         name: Some("node-1".into()),
+    ///         ..ObjectReference::default()
+    ///     },
+    /// };
+    /// let data = serde_json::to_vec(&binding)?;
+    /// let _: Status = pods.create_subresource("binding", "blog", &PostParams::default(), data).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_subresource<T>(
+        &self,
+        subresource_name: &str,
+        name: &str,
+        pp: &PostParams,
+        data: Vec<u8>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let mut req = self
+            .request
+            .create_subresource(subresource_name, name, pp, data)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("create_subresource");
+        self.override_accept(&mut req);
+        self.client.request::<T>(req).await
+    }
+
     /// Patch an instance of the subresource
     pub async fn patch_subresource<P: serde::Serialize + Debug>(
         &self,
@@ -89,6 +167,7 @@ where
             .patch_subresource(subresource_name, name, pp, patch)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("patch_subresource");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
@@ -105,6 +184,7 @@ where
             .replace_subresource(subresource_name, name, pp, data)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("replace_subresource");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 }
@@ -127,6 +207,7 @@ where
             .get_subresource("status", name)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("get_status");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
@@ -164,6 +245,7 @@ where
             .patch_subresource("status", name, pp, patch)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("patch_status");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 
@@ -192,6 +274,60 @@ where
             .replace_subresource("status", name, pp, data)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("replace_status");
+        self.override_accept(&mut req);
+        self.client.request::<K>(req).await
+    }
+}
+
+impl<K> Api<K>
+where
+    K: Resource + DeserializeOwned,
+    K::DynamicType: Default,
+{
+    /// Server-side apply fields on the status object
+    ///
+    /// The `status` is applied as the status of the named object, and the fields it contains
+    /// become owned by `field_manager`. This lets several controllers own distinct status fields
+    /// (e.g. separate conditions) without overwriting each other.
+    /// Set `force` to take ownership of fields currently owned by other managers.
+    ///
+    /// NB: Requires that the resource has a status subresource.
+    ///
+    /// ```no_run
+    /// use kube::{api::Api, Client};
+    /// use k8s_openapi::api::batch::v1::Job;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let jobs: Api<Job> = Api::namespaced(client, "apps");
+    ///     let status = serde_json::json!({ "succeeded": 2 });
+    ///     let o = jobs.apply_status("baz", "myapp", &status, false).await?;
+    ///     assert_eq!(o.status.unwrap().succeeded, Some(2));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn apply_status<S: serde::Serialize>(
+        &self,
+        name: &str,
+        field_manager: &str,
+        status: &S,
+        force: bool,
+    ) -> Result<K> {
+        let dt = K::DynamicType::default();
+        let patch = serde_json::json!({
+            "apiVersion": K::api_version(&dt),
+            "kind": K::kind(&dt),
+            "metadata": { "name": name },
+            "status": status,
+        });
+        let mut pp = PatchParams::apply(field_manager);
+        pp.force = force;
+        let mut req = self
+            .request
+            .patch_subresource("status", name, &pp, &Patch::Apply(patch))
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("apply_status");
+        self.override_accept(&mut req);
         self.client.request::<K>(req).await
     }
 }
@@ -261,9 +397,14 @@ where
     K: DeserializeOwned + Evict,
 {
     /// Create an eviction
+    ///
+    /// Unlike [`Api::delete`], an eviction respects any `PodDisruptionBudget` covering the pod.
+    /// When evicting the pod would violate a budget, this returns an [`Error::Api`] with code `429`
+    /// and reason `"TooManyRequests"`; the eviction can be retried after backing off.
     pub async fn evict(&self, name: &str, ep: &EvictParams) -> Result<Status> {
         let mut req = self.request.evict(name, ep).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("evict");
+        self.override_accept(&mut req);
         self.client.request::<Status>(req).await
     }
 }
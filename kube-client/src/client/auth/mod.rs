@@ -2,25 +2,29 @@ use std::{
     path::{Path, PathBuf},
     process::Command,
     sync::Arc,
+    task::{Context, Poll},
 };
 
 use chrono::{DateTime, Duration, Utc};
 use futures::future::BoxFuture;
+use bytes::Bytes;
 use http::{
     header::{InvalidHeaderValue, AUTHORIZATION},
-    HeaderValue, Request,
+    HeaderValue, Request, Response, StatusCode,
 };
 use jsonpath_lib::select as jsonpath_select;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::{Mutex, RwLock};
-use tower::{filter::AsyncPredicate, BoxError};
+use tower::{BoxError, Layer, Service, ServiceExt};
 
 use crate::config::{AuthInfo, AuthProviderConfig, ExecConfig};
 
 #[cfg(feature = "oauth")] mod oauth;
 #[cfg(feature = "oauth")] pub use oauth::Error as OAuthError;
+#[cfg(feature = "oidc")] mod oidc;
+#[cfg(feature = "oidc")] pub use oidc::Error as OidcError;
 
 #[derive(Error, Debug)]
 /// Client auth errors
@@ -81,6 +85,12 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "oauth")))]
     #[error("failed OAuth: {0}")]
     OAuth(#[source] OAuthError),
+
+    /// OIDC error
+    #[cfg(feature = "oidc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "oidc")))]
+    #[error("failed OIDC: {0}")]
+    Oidc(#[source] OidcError),
 }
 
 #[derive(Debug, Clone)]
@@ -144,6 +154,7 @@ impl TokenFile {
 // - token-file refreshed at least once per minute
 // - gcp: command based token source (exec)
 // - gcp: application credential based token source (requires `oauth` feature)
+// - oidc: id-token refreshed with its refresh-token when expiring or rejected (requires `oidc` feature)
 //
 // Note that the visibility must be `pub` for `impl Layer for AuthLayer`, but this is not exported from the crate.
 // It's not accessible from outside and not shown on docs.
@@ -153,28 +164,134 @@ pub enum RefreshableToken {
     File(Arc<RwLock<TokenFile>>),
     #[cfg(feature = "oauth")]
     GcpOauth(Arc<Mutex<oauth::Gcp>>),
+    #[cfg(feature = "oidc")]
+    Oidc(Arc<Mutex<oidc::Oidc>>),
+}
+
+/// Layer that sets the `Authorization` header from a [`RefreshableToken`]
+///
+/// Requests rejected with `401 Unauthorized` are sent once more with a refreshed token
+/// when the token can be refreshed on demand, which requires buffering their bodies.
+#[derive(Debug, Clone)]
+pub struct RefreshTokenLayer(pub(crate) RefreshableToken);
+
+impl<S> Layer<S> for RefreshTokenLayer {
+    type Service = RefreshToken<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RefreshToken {
+            inner,
+            token: self.0.clone(),
+        }
+    }
+}
+
+/// Service that sets the `Authorization` header from a [`RefreshableToken`]
+#[derive(Debug, Clone)]
+pub struct RefreshToken<S> {
+    inner: S,
+    token: RefreshableToken,
 }
 
-// For use with `AsyncFilterLayer` to add `Authorization` header with a refreshed token.
-impl<B> AsyncPredicate<Request<B>> for RefreshableToken
+impl<S, B, ResBody> Service<Request<B>> for RefreshToken<S>
 where
-    B: http_body::Body + Send + 'static,
+    S: Service<Request<B>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: http_body::Body + From<Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    ResBody: Send + 'static,
 {
-    type Future = BoxFuture<'static, Result<Request<B>, BoxError>>;
-    type Request = Request<B>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = Response<ResBody>;
 
-    fn check(&mut self, mut request: Self::Request) -> Self::Future {
-        let refreshable = self.clone();
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        // Keep the service that was polled ready for the first attempt
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let token = self.token.clone();
         Box::pin(async move {
-            refreshable.to_header().await.map_err(Into::into).map(|value| {
-                request.headers_mut().insert(AUTHORIZATION, value);
-                request
-            })
+            let header = token.to_header().await?;
+            if !token.refreshes_unauthorized() {
+                request.headers_mut().insert(AUTHORIZATION, header);
+                return inner.call(request).await.map_err(Into::into);
+            }
+
+            let (parts, body) = request.into_parts();
+            let body = hyper::body::to_bytes(body).await.map_err(Into::into)?;
+            let mut retry = Request::new(B::from(body.clone()));
+            *retry.method_mut() = parts.method.clone();
+            *retry.uri_mut() = parts.uri.clone();
+            *retry.version_mut() = parts.version;
+            *retry.headers_mut() = parts.headers.clone();
+            // Extensions cannot be cloned, but keep the operation name used for tracing
+            if let Some(name) = parts.extensions.get::<&'static str>() {
+                retry.extensions_mut().insert(*name);
+            }
+
+            let mut request = Request::from_parts(parts, B::from(body));
+            request.headers_mut().insert(AUTHORIZATION, header.clone());
+            let res = inner.call(request).await.map_err(Into::into)?;
+            if res.status() != StatusCode::UNAUTHORIZED {
+                return Ok(res);
+            }
+            match token.refresh_rejected(&header).await? {
+                Some(header) => {
+                    tracing::debug!("retrying request rejected as unauthorized with a refreshed token");
+                    retry.headers_mut().insert(AUTHORIZATION, header);
+                    inner
+                        .ready()
+                        .await
+                        .map_err(Into::into)?
+                        .call(retry)
+                        .await
+                        .map_err(Into::into)
+                }
+                None => Ok(res),
+            }
         })
     }
 }
 
 impl RefreshableToken {
+    // Whether a request rejected with `401 Unauthorized` may be accepted with a refreshed token
+    fn refreshes_unauthorized(&self) -> bool {
+        match self {
+            #[cfg(feature = "oidc")]
+            RefreshableToken::Oidc(_) => true,
+            _ => false,
+        }
+    }
+
+    // Refreshes the token after the apiserver rejected the `rejected` header,
+    // returning the header to retry with if there is a new token.
+    #[cfg_attr(not(feature = "oidc"), allow(unused_variables))]
+    async fn refresh_rejected(&self, rejected: &HeaderValue) -> Result<Option<HeaderValue>, Error> {
+        match self {
+            #[cfg(feature = "oidc")]
+            RefreshableToken::Oidc(data) => {
+                let rejected = rejected
+                    .to_str()
+                    .ok()
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    .unwrap_or_default();
+                let mut oidc = data.lock().await;
+                if oidc.refresh_rejected(rejected).await.map_err(Error::Oidc)? {
+                    bearer_header(oidc.id_token().await.map_err(Error::Oidc)?).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
     async fn to_header(&self) -> Result<HeaderValue, Error> {
         match self {
             RefreshableToken::Exec(data) => {
@@ -201,6 +318,8 @@ impl RefreshableToken {
                         Auth::RefreshableToken(RefreshableToken::File(_)) => unreachable!(),
                         #[cfg(feature = "oauth")]
                         Auth::RefreshableToken(RefreshableToken::GcpOauth(_)) => unreachable!(),
+                        #[cfg(feature = "oidc")]
+                        Auth::RefreshableToken(RefreshableToken::Oidc(_)) => unreachable!(),
                     }
                 }
 
@@ -225,6 +344,12 @@ impl RefreshableToken {
                 let token = (*gcp_oauth).token().await.map_err(Error::OAuth)?;
                 bearer_header(&token.access_token)
             }
+
+            #[cfg(feature = "oidc")]
+            RefreshableToken::Oidc(data) => {
+                let mut oidc = data.lock().await;
+                bearer_header(oidc.id_token().await.map_err(Error::Oidc)?)
+            }
         }
     }
 }
@@ -244,10 +369,18 @@ impl TryFrom<&AuthInfo> for Auth {
     fn try_from(auth_info: &AuthInfo) -> Result<Self, Self::Error> {
         if let Some(provider) = &auth_info.auth_provider {
             match token_from_provider(provider)? {
+                #[cfg(not(feature = "oidc"))]
                 ProviderToken::Oidc(token) => {
                     return Ok(Self::Bearer(SecretString::from(token)));
                 }
 
+                #[cfg(feature = "oidc")]
+                ProviderToken::Oidc(oidc) => {
+                    return Ok(Self::RefreshableToken(RefreshableToken::Oidc(Arc::new(
+                        Mutex::new(oidc),
+                    ))));
+                }
+
                 ProviderToken::GcpCommand(token, Some(expiry)) => {
                     let mut info = auth_info.clone();
                     let mut provider = provider.clone();
@@ -311,7 +444,10 @@ impl TryFrom<&AuthInfo> for Auth {
 
 // We need to differentiate providers because the keys/formats to store token expiration differs.
 enum ProviderToken {
+    #[cfg(not(feature = "oidc"))]
     Oidc(String),
+    #[cfg(feature = "oidc")]
+    Oidc(oidc::Oidc),
     // "access-token", "expiry" (RFC3339)
     GcpCommand(String, Option<DateTime<Utc>>),
     #[cfg(feature = "oauth")]
@@ -331,6 +467,14 @@ fn token_from_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, E
     }
 }
 
+#[cfg(feature = "oidc")]
+fn token_from_oidc_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, Error> {
+    oidc::Oidc::from_config(&provider.config)
+        .map(ProviderToken::Oidc)
+        .map_err(Error::Oidc)
+}
+
+#[cfg(not(feature = "oidc"))]
 fn token_from_oidc_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, Error> {
     match provider.config.get("id-token") {
         Some(id_token) => Ok(ProviderToken::Oidc(id_token.clone())),
@@ -474,6 +618,16 @@ fn auth_exec(auth: &ExecConfig) -> Result<ExecCredential, Error> {
             });
         cmd.envs(envs);
     }
+    // Tell the plugin which ExecCredential version to respond with, see
+    // https://kubernetes.io/docs/reference/access-authn-authz/authentication/#input-and-output-formats
+    let mut exec_info = serde_json::json!({
+        "kind": "ExecCredential",
+        "spec": { "interactive": false },
+    });
+    if let Some(api_version) = &auth.api_version {
+        exec_info["apiVersion"] = api_version.as_str().into();
+    }
+    cmd.env("KUBERNETES_EXEC_INFO", exec_info.to_string());
     let out = cmd.output().map_err(Error::AuthExecStart)?;
     if !out.status.success() {
         return Err(Error::AuthExecRun {
@@ -539,6 +693,24 @@ mod test {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn exec_plugin_receives_exec_info() {
+        let exec = ExecConfig {
+            api_version: Some("client.authentication.k8s.io/v1beta1".into()),
+            command: "sh".into(),
+            args: Some(vec!["-c".into(), "echo \"$KUBERNETES_EXEC_INFO\"".into()]),
+            env: None,
+        };
+        let creds = auth_exec(&exec).unwrap();
+        assert_eq!(creds.kind.as_deref(), Some("ExecCredential"));
+        assert_eq!(
+            creds.api_version.as_deref(),
+            Some("client.authentication.k8s.io/v1beta1")
+        );
+        assert!(creds.status.is_none());
+    }
+
     #[test]
     fn token_file() {
         let file = tempfile::NamedTempFile::new().unwrap();
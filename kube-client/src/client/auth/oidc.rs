@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use http::{header::CONTENT_TYPE, Request, StatusCode};
+use hyper::Body;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{de::DeserializeOwned, Deserialize};
+use thiserror::Error;
+
+use crate::client::tls;
+
+#[derive(Error, Debug)]
+/// Possible errors when refreshing an OIDC id-token
+pub enum Error {
+    /// The auth-provider config is missing a required key
+    #[error("oidc auth-provider config is missing `{0}`")]
+    MissingConfig(&'static str),
+
+    /// Failed to build a request
+    #[error("failed to build request: {0}")]
+    BuildRequest(#[source] http::Error),
+
+    /// Failed to send a request to the identity provider
+    #[error("failed to send request to identity provider: {0}")]
+    SendRequest(#[source] hyper::Error),
+
+    /// Failed to concatenate the buffers from response body
+    #[error("failed to concatenate the buffers from response body: {0}")]
+    ConcatBuffers(#[source] hyper::Error),
+
+    /// The identity provider rejected the request
+    #[error("identity provider responded with {0}: {1}")]
+    Rejected(StatusCode, String),
+
+    /// Failed to parse the identity provider response
+    #[error("failed to parse identity provider response: {0}")]
+    ParseResponse(#[source] serde_json::Error),
+
+    /// Failed to read the identity provider's certificate authority file
+    #[error("failed to read idp-certificate-authority: {0}")]
+    ReadCertificateAuthority(#[source] std::io::Error),
+
+    /// Failed to decode the identity provider's certificate authority data
+    #[error("failed to decode idp-certificate-authority-data: {0}")]
+    DecodeCertificateAuthority(#[source] base64::DecodeError),
+
+    /// Failed to parse the identity provider's certificate authority
+    #[error("failed to parse the identity provider's certificate authority: {0}")]
+    ParseCertificateAuthority(#[source] pem::PemError),
+
+    /// Failed to create OpenSSL HTTPS connector
+    #[cfg(feature = "openssl-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "openssl-tls")))]
+    #[error("failed to create OpenSSL HTTPS connector: {0}")]
+    CreateOpensslHttpsConnector(#[source] openssl::error::ErrorStack),
+
+    /// Failed to create OpenSSL SSL connector
+    #[cfg(feature = "openssl-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "openssl-tls")))]
+    #[error("failed to create OpenSSL SSL connector: {0}")]
+    CreateOpensslSslConnector(#[source] tls::openssl_tls::SslConnectorError),
+
+    /// Failed to create native TLS connector
+    #[cfg(feature = "native-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    #[error("failed to create native TLS connector: {0}")]
+    CreateNativeTlsConnector(#[source] tls::native_tls::Error),
+
+    /// Failed to create rustls client config
+    #[cfg(feature = "rustls-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rustls-tls")))]
+    #[error("failed to create rustls client config: {0}")]
+    CreateRustlsClientConfig(#[source] tls::rustls_tls::Error),
+}
+
+/// The id-token of an `oidc` auth-provider, refreshed using its refresh-token
+///
+/// The id-token is refreshed ahead of its `exp` claim, and again whenever the apiserver rejects it.
+/// Tokens without an `exp` claim are only refreshed once rejected.
+#[derive(Debug)]
+pub struct Oidc {
+    id_token: SecretString,
+    expiry: Option<DateTime<Utc>>,
+    refresher: Option<Refresher>,
+}
+
+// What is needed to run the refresh-token grant against the identity provider.
+#[derive(Debug)]
+struct Refresher {
+    issuer_url: String,
+    client_id: String,
+    client_secret: Option<SecretString>,
+    refresh_token: SecretString,
+    // DER-encoded certificates to trust for the identity provider, instead of the system roots
+    root_certs: Option<Vec<Vec<u8>>>,
+}
+
+impl Oidc {
+    // Mirrors the keys used by client-go's oidc auth-provider.
+    // Without a `refresh-token`, the `id-token` is used as is until the apiserver rejects it.
+    pub(crate) fn from_config(config: &HashMap<String, String>) -> Result<Self, Error> {
+        let id_token = config.get("id-token").ok_or(Error::MissingConfig("id-token"))?;
+        let refresher = match config.get("refresh-token") {
+            Some(refresh_token) => Some(Refresher {
+                issuer_url: config
+                    .get("idp-issuer-url")
+                    .ok_or(Error::MissingConfig("idp-issuer-url"))?
+                    .clone(),
+                client_id: config
+                    .get("client-id")
+                    .ok_or(Error::MissingConfig("client-id"))?
+                    .clone(),
+                client_secret: config.get("client-secret").cloned().map(SecretString::from),
+                refresh_token: SecretString::from(refresh_token.clone()),
+                root_certs: idp_root_certs(config)?,
+            }),
+            None => None,
+        };
+        Ok(Self {
+            expiry: id_token_expiry(id_token),
+            id_token: SecretString::from(id_token.clone()),
+            refresher,
+        })
+    }
+
+    fn is_expiring(&self) -> bool {
+        // Add some wiggle room so the token does not expire while the request is in flight
+        self.expiry
+            .map_or(false, |expiry| Utc::now() + Duration::seconds(60) >= expiry)
+    }
+
+    /// Get the id-token, refreshing it first if it is expiring and a refresh-token is available
+    pub async fn id_token(&mut self) -> Result<&str, Error> {
+        if self.is_expiring() {
+            self.refresh().await?;
+        }
+        Ok(self.id_token.expose_secret())
+    }
+
+    /// Refresh the id-token after the apiserver rejected `rejected`
+    ///
+    /// Returns whether a different id-token is available to retry with. The id-token is only
+    /// refreshed if it is still the rejected one, so that concurrent rejections refresh it once.
+    pub async fn refresh_rejected(&mut self, rejected: &str) -> Result<bool, Error> {
+        if self.id_token.expose_secret() != rejected {
+            return Ok(true);
+        }
+        self.refresh().await
+    }
+
+    // Returns whether the id-token was refreshed, which requires a refresh-token
+    async fn refresh(&mut self) -> Result<bool, Error> {
+        let refresher = match &mut self.refresher {
+            Some(refresher) => refresher,
+            None => return Ok(false),
+        };
+        let id_token = refresher.refresh().await?;
+        self.expiry = id_token_expiry(&id_token);
+        self.id_token = SecretString::from(id_token);
+        Ok(true)
+    }
+}
+
+// The certificate authority of the identity provider, read like client-go does
+fn idp_root_certs(config: &HashMap<String, String>) -> Result<Option<Vec<Vec<u8>>>, Error> {
+    let pem = if let Some(data) = config.get("idp-certificate-authority-data") {
+        base64::decode(data).map_err(Error::DecodeCertificateAuthority)?
+    } else if let Some(file) = config.get("idp-certificate-authority") {
+        std::fs::read(file).map_err(Error::ReadCertificateAuthority)?
+    } else {
+        return Ok(None);
+    };
+    crate::config::certs(&pem)
+        .map(Some)
+        .map_err(Error::ParseCertificateAuthority)
+}
+
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    token_endpoint: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+    refresh_token: Option<String>,
+}
+
+impl Refresher {
+    async fn refresh(&mut self) -> Result<String, Error> {
+        #[cfg(not(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls")))]
+        compile_error!(
+            "At least one of native-tls or rustls-tls or openssl-tls feature must be enabled to use oidc feature"
+        );
+        // Same TLS feature precedence as the oauth provider
+        #[cfg(feature = "openssl-tls")]
+        let https = {
+            let mut http = hyper::client::HttpConnector::new();
+            http.enforce_http(false);
+            let ssl = tls::openssl_tls::ssl_connector_builder(None, self.root_certs.as_ref())
+                .map_err(Error::CreateOpensslSslConnector)?;
+            hyper_openssl::HttpsConnector::with_connector(http, ssl)
+                .map_err(Error::CreateOpensslHttpsConnector)?
+        };
+        #[cfg(all(not(feature = "openssl-tls"), feature = "native-tls"))]
+        let https = {
+            let mut http = hyper::client::HttpConnector::new();
+            http.enforce_http(false);
+            let tls = tls::native_tls::native_tls_connector(None, self.root_certs.as_ref(), false)
+                .map_err(Error::CreateNativeTlsConnector)?;
+            hyper_tls::HttpsConnector::from((http, tokio_native_tls::TlsConnector::from(tls)))
+        };
+        #[cfg(all(
+            not(any(feature = "openssl-tls", feature = "native-tls")),
+            feature = "rustls-tls"
+        ))]
+        let https = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(
+                tls::rustls_tls::rustls_client_config(None, self.root_certs.as_deref(), false)
+                    .map_err(Error::CreateRustlsClientConfig)?,
+            )
+            .https_only()
+            .enable_http1()
+            .build();
+        let client = hyper::Client::builder().build::<_, Body>(https);
+
+        let discovery_url = format!(
+            "{}/.well-known/openid-configuration",
+            self.issuer_url.trim_end_matches('/')
+        );
+        let request = Request::get(discovery_url)
+            .body(Body::empty())
+            .map_err(Error::BuildRequest)?;
+        let metadata: ProviderMetadata = send(&client, request).await?;
+
+        let form = {
+            let mut form = form_urlencoded::Serializer::new(String::new());
+            form.append_pair("grant_type", "refresh_token")
+                .append_pair("refresh_token", self.refresh_token.expose_secret())
+                .append_pair("client_id", &self.client_id);
+            if let Some(client_secret) = &self.client_secret {
+                form.append_pair("client_secret", client_secret.expose_secret());
+            }
+            form.finish()
+        };
+        let request = Request::post(metadata.token_endpoint)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .map_err(Error::BuildRequest)?;
+        let token: TokenResponse = send(&client, request).await?;
+
+        // Identity providers may rotate the refresh-token on every use
+        if let Some(refresh_token) = token.refresh_token {
+            self.refresh_token = SecretString::from(refresh_token);
+        }
+        Ok(token.id_token)
+    }
+}
+
+async fn send<C, T>(client: &hyper::Client<C>, request: Request<Body>) -> Result<T, Error>
+where
+    C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    T: DeserializeOwned,
+{
+    let res = client.request(request).await.map_err(Error::SendRequest)?;
+    let status = res.status();
+    let bytes = hyper::body::to_bytes(res.into_body())
+        .await
+        .map_err(Error::ConcatBuffers)?;
+    if !status.is_success() {
+        return Err(Error::Rejected(
+            status,
+            String::from_utf8_lossy(&bytes).into_owned(),
+        ));
+    }
+    serde_json::from_slice(&bytes).map_err(Error::ParseResponse)
+}
+
+// The `exp` claim of a JWT, without verifying it. Verification is up to the apiserver.
+fn id_token_expiry(id_token: &str) -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: i64,
+    }
+    let payload = id_token.split('.').nth(1)?;
+    let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+    let claims: Claims = serde_json::from_slice(&payload).ok()?;
+    Utc.timestamp_opt(claims.exp, 0).single()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn jwt(claims: serde_json::Value) -> String {
+        let encode = |v: &serde_json::Value| base64::encode_config(v.to_string(), base64::URL_SAFE_NO_PAD);
+        format!(
+            "{}.{}.signature",
+            encode(&serde_json::json!({ "alg": "RS256" })),
+            encode(&claims)
+        )
+    }
+
+    #[test]
+    fn id_token_expiry_reads_exp_claim() {
+        let token = jwt(serde_json::json!({ "iss": "https://idp.example.com", "exp": 1_600_000_000 }));
+        assert_eq!(
+            id_token_expiry(&token),
+            Utc.timestamp_opt(1_600_000_000, 0).single()
+        );
+        assert_eq!(id_token_expiry(&jwt(serde_json::json!({}))), None);
+        assert_eq!(id_token_expiry("not-a-jwt"), None);
+    }
+
+    #[tokio::test]
+    async fn unrefreshable_id_token_is_used_as_is() {
+        let expired = jwt(serde_json::json!({ "exp": 1_600_000_000 }));
+        let config = HashMap::from([("id-token".to_string(), expired.clone())]);
+        let mut oidc = Oidc::from_config(&config).unwrap();
+        assert!(oidc.is_expiring());
+        assert_eq!(oidc.id_token().await.unwrap(), expired);
+    }
+
+    #[test]
+    fn refresh_token_requires_issuer_and_client() {
+        let config = HashMap::from([
+            ("id-token".to_string(), "token".to_string()),
+            ("refresh-token".to_string(), "refresh".to_string()),
+            ("client-id".to_string(), "kubernetes".to_string()),
+        ]);
+        assert!(matches!(
+            Oidc::from_config(&config),
+            Err(Error::MissingConfig("idp-issuer-url"))
+        ));
+    }
+
+    #[test]
+    fn idp_certificate_authority_is_loaded() {
+        assert!(idp_root_certs(&HashMap::new()).unwrap().is_none());
+        let config = HashMap::from([(
+            "idp-certificate-authority-data".to_string(),
+            "not base64".to_string(),
+        )]);
+        assert!(matches!(
+            idp_root_certs(&config),
+            Err(Error::DecodeCertificateAuthority(_))
+        ));
+        let config = HashMap::from([(
+            "idp-certificate-authority".to_string(),
+            "/does/not/exist.pem".to_string(),
+        )]);
+        assert!(matches!(
+            idp_root_certs(&config),
+            Err(Error::ReadCertificateAuthority(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn id_token_without_expiry_is_refreshed_when_rejected() {
+        let config = HashMap::from([("id-token".to_string(), jwt(serde_json::json!({})))]);
+        let mut oidc = Oidc::from_config(&config).unwrap();
+        assert!(!oidc.is_expiring());
+        // Nothing to refresh with, so there is nothing to retry with either
+        let current = oidc.id_token().await.unwrap().to_string();
+        assert!(!oidc.refresh_rejected(&current).await.unwrap());
+        // Another request already replaced the rejected id-token
+        assert!(oidc.refresh_rejected("stale").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn unauthorized_requests_are_retried_with_a_new_id_token() {
+        use crate::client::auth::{RefreshTokenLayer, RefreshableToken};
+        use futures::pin_mut;
+        use http::{header::AUTHORIZATION, Response};
+        use std::sync::Arc;
+        use tokio::sync::Mutex;
+        use tower::{Layer, ServiceExt};
+        use tower_test::mock;
+
+        let config = HashMap::from([("id-token".to_string(), "old".to_string())]);
+        let oidc = Arc::new(Mutex::new(Oidc::from_config(&config).unwrap()));
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let service = RefreshTokenLayer(RefreshableToken::Oidc(oidc.clone())).layer(mock_service);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[AUTHORIZATION], "Bearer old");
+            // Refreshed by another request while this one was in flight
+            oidc.lock().await.id_token = SecretString::from("new".to_string());
+            send.send_response(Response::builder().status(401).body(Body::empty()).unwrap());
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::POST);
+            assert_eq!(request.headers()[AUTHORIZATION], "Bearer new");
+            assert_eq!(hyper::body::to_bytes(request.into_body()).await.unwrap(), "{}");
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+        });
+
+        let request = Request::post("/api/v1/namespaces").body(Body::from("{}")).unwrap();
+        let res = service.oneshot(request).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        spawned.await.unwrap();
+    }
+}
@@ -3,13 +3,22 @@ use http::{Request, Response};
 use hyper::{self, client::HttpConnector};
 use hyper_timeout::TimeoutConnector;
 pub use kube_core::response::Status;
-use tower::{util::BoxService, BoxError, Layer, Service, ServiceBuilder};
+use tower::{
+    util::{BoxService, MapRequest},
+    BoxError, Layer, Service, ServiceBuilder,
+};
 use tower_http::{
-    classify::ServerErrorsFailureClass, map_response_body::MapResponseBodyLayer, trace::TraceLayer,
+    classify::ServerErrorsFailureClass,
+    map_response_body::MapResponseBodyLayer,
+    set_header::{SetRequestHeader, SetRequestHeaderLayer},
+    trace::TraceLayer,
 };
 
 use crate::{client::ConfigExt, Client, Config, Error, Result};
 
+/// The `User-Agent` sent by clients built from a [`Config`], unless overridden by [`ClientBuilder::user_agent`]
+pub const DEFAULT_USER_AGENT: &str = concat!("kube-rs/", env!("CARGO_PKG_VERSION"));
+
 /// HTTP body of a dynamic backing type.
 ///
 /// The suggested implementation type is [`hyper::Body`].
@@ -48,6 +57,62 @@ impl<Svc> ClientBuilder<Svc> {
         }
     }
 
+    /// Run `f` on every request before it is handed to the current [`Service`] stack.
+    ///
+    /// This is a lightweight alternative to [`ClientBuilder::with_layer`] for adjusting requests,
+    /// e.g. to inject tracing context or tenant headers. It applies to all requests, including watches.
+    ///
+    /// ```no_run
+    /// use kube::{client::ClientBuilder, Client, Config};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = Config::infer().await?;
+    ///     let client: Client = ClientBuilder::try_from(config)?
+    ///         .map_request(|mut req| {
+    ///             req.headers_mut().insert("x-tenant-id", "blue".parse().unwrap());
+    ///             req
+    ///         })
+    ///         .build();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn map_request<F>(self, f: F) -> ClientBuilder<MapRequest<Svc, F>>
+    where
+        F: FnMut(Request<hyper::Body>) -> Request<hyper::Body>,
+    {
+        let Self { service, default_ns } = self;
+        ClientBuilder {
+            service: MapRequest::new(service, f),
+            default_ns,
+        }
+    }
+
+    /// Set the `User-Agent` header on every request, replacing the default of [`DEFAULT_USER_AGENT`]
+    ///
+    /// This identifies the client in the apiserver's audit logs.
+    ///
+    /// ```no_run
+    /// use kube::{client::ClientBuilder, Client, Config};
+    /// use http::HeaderValue;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = Config::infer().await?;
+    ///     let client: Client = ClientBuilder::try_from(config)?
+    ///         .user_agent(HeaderValue::from_static("my-controller/1.0"))
+    ///         .build();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn user_agent(
+        self,
+        user_agent: http::HeaderValue,
+    ) -> ClientBuilder<SetRequestHeader<Svc, http::HeaderValue>> {
+        self.with_layer(&SetRequestHeaderLayer::overriding(
+            http::header::USER_AGENT,
+            user_agent,
+        ))
+    }
+
     /// Build a [`Client`] instance with the current [`Service`] stack.
     pub fn build<B>(self) -> Client
     where
@@ -102,21 +167,28 @@ impl TryFrom<Config> for ClientBuilder<BoxService<Request<hyper::Body>, Response
             let mut connector = TimeoutConnector::new(connector);
 
             // Set the timeout for the client and fallback to default deprecated timeout until it's removed
+            // The read timeout is applied per request by `ReadTimeoutLayer` so that watches can outlive it
             #[allow(deprecated)]
             {
                 connector.set_connect_timeout(config.connect_timeout.or(config.timeout));
-                connector.set_read_timeout(config.read_timeout.or(config.timeout));
                 connector.set_write_timeout(config.write_timeout);
             }
 
             hyper::Client::builder().build(connector)
         };
 
-        let stack = ServiceBuilder::new().layer(config.base_uri_layer()).into_inner();
+        let stack = ServiceBuilder::new()
+            .layer(SetRequestHeaderLayer::if_not_present(
+                http::header::USER_AGENT,
+                http::HeaderValue::from_static(DEFAULT_USER_AGENT),
+            ))
+            .layer(config.read_timeout_layer())
+            .layer(config.base_uri_layer())
+            .into_inner();
         #[cfg(feature = "gzip")]
         let stack = ServiceBuilder::new()
             .layer(stack)
-            .layer(tower_http::decompression::DecompressionLayer::new())
+            .layer(tower_http::decompression::DecompressionLayer::new().gzip(!config.disable_compression))
             .into_inner();
 
         let service = ServiceBuilder::new()
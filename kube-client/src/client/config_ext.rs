@@ -2,13 +2,13 @@ use std::sync::Arc;
 
 use http::{header::HeaderName, HeaderValue};
 use secrecy::ExposeSecret;
-use tower::{filter::AsyncFilterLayer, util::Either};
+use tower::util::Either;
 
 #[cfg(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls"))]
 use super::tls;
 use super::{
-    auth::Auth,
-    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer},
+    auth::{Auth, RefreshTokenLayer},
+    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer, ReadTimeoutLayer},
 };
 use crate::{Config, Error, Result};
 
@@ -27,6 +27,11 @@ pub trait ConfigExt: private::Sealed {
     /// Layer to add non-authn HTTP headers depending on the config.
     fn extra_headers_layer(&self) -> Result<ExtraHeadersLayer>;
 
+    /// Layer to time out requests when the apiserver stops sending data for longer than the read timeout.
+    ///
+    /// Watch requests are exempt for as long as the apiserver is expected to keep them open.
+    fn read_timeout_layer(&self) -> ReadTimeoutLayer;
+
     /// Create [`hyper_tls::HttpsConnector`] based on config.
     ///
     /// # Example
@@ -183,11 +188,18 @@ impl ConfigExt for Config {
                 AddAuthorizationLayer::bearer(token.expose_secret()).as_sensitive(true),
             ))),
             Auth::RefreshableToken(refreshable) => {
-                Some(AuthLayer(Either::B(AsyncFilterLayer::new(refreshable))))
+                Some(AuthLayer(Either::B(RefreshTokenLayer(refreshable))))
             }
         })
     }
 
+    fn read_timeout_layer(&self) -> ReadTimeoutLayer {
+        #[allow(deprecated)]
+        ReadTimeoutLayer {
+            timeout: self.read_timeout.or(self.timeout),
+        }
+    }
+
     fn extra_headers_layer(&self) -> Result<ExtraHeadersLayer> {
         let mut headers = Vec::new();
         if let Some(impersonate_user) = &self.auth_info.impersonate {
@@ -208,6 +220,20 @@ impl ConfigExt for Config {
                 ));
             }
         }
+        if let Some(impersonate_uid) = &self.auth_info.impersonate_uid {
+            headers.push((
+                HeaderName::from_static("impersonate-uid"),
+                HeaderValue::from_str(impersonate_uid)
+                    .map_err(http::Error::from)
+                    .map_err(Error::HttpError)?,
+            ));
+        }
+        if self.disable_compression {
+            headers.push((
+                http::header::ACCEPT_ENCODING,
+                HeaderValue::from_static("identity"),
+            ));
+        }
         Ok(ExtraHeadersLayer {
             headers: Arc::new(headers),
         })
@@ -279,3 +305,43 @@ impl ConfigExt for Config {
         Ok(https)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigExt;
+    use crate::Config;
+
+    #[test]
+    fn disable_compression_sets_identity_encoding() {
+        let mut config = Config::new("http://localhost:8080".parse().unwrap());
+        let layer = config.extra_headers_layer().unwrap();
+        assert!(layer.headers.is_empty());
+
+        config.disable_compression = true;
+        let layer = config.extra_headers_layer().unwrap();
+        assert_eq!(*layer.headers, vec![(
+            http::header::ACCEPT_ENCODING,
+            http::HeaderValue::from_static("identity")
+        )]);
+    }
+
+    #[test]
+    fn impersonation_sets_headers() {
+        let mut config = Config::new("http://localhost:8080".parse().unwrap());
+        config.auth_info.impersonate = Some("jane".into());
+        config.auth_info.impersonate_groups = Some(vec!["devs".into(), "ops".into()]);
+        config.auth_info.impersonate_uid = Some("1234".into());
+        let layer = config.extra_headers_layer().unwrap();
+        let headers = layer
+            .headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(headers, vec![
+            ("impersonate-user", "jane"),
+            ("impersonate-group", "devs"),
+            ("impersonate-group", "ops"),
+            ("impersonate-uid", "1234"),
+        ]);
+    }
+}
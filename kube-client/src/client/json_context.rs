@@ -0,0 +1,155 @@
+//! Locating deserialization errors within the JSON that caused them
+use std::fmt;
+
+/// Number of bytes of JSON shown on each side of an error
+const SNIPPET_RADIUS: usize = 100;
+
+/// Where in a JSON document a [`serde_json::Error`] occurred
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct JsonErrorContext {
+    /// The path to the value being deserialized, e.g. `object.spec.ports[0].port`
+    pub(crate) path: String,
+    /// The JSON surrounding the error, truncated to [`SNIPPET_RADIUS`] bytes on each side
+    pub(crate) snippet: String,
+}
+
+impl JsonErrorContext {
+    /// Locates `err` within the single-line `json` it was raised for
+    pub(crate) fn new(json: &str, err: &serde_json::Error) -> Self {
+        // serde_json columns are 1-based and point at the last byte consumed
+        let offset = floor_char_boundary(json, err.column().min(json.len()));
+        Self {
+            path: path_at(json, offset),
+            snippet: snippet_around(json, offset),
+        }
+    }
+}
+
+impl fmt::Display for JsonErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at `{}` near {}", self.path, self.snippet)
+    }
+}
+
+enum Segment {
+    Key(Option<String>),
+    Index(usize),
+}
+
+/// Renders the path of the innermost value that is open at byte `offset` of `json`
+fn path_at(json: &str, offset: usize) -> String {
+    let mut stack = Vec::new();
+    let mut expecting_key = false;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut key = None;
+    for c in json[..offset].chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+                if let (Some(k), Some(Segment::Key(current))) = (key.take(), stack.last_mut()) {
+                    *current = Some(k);
+                }
+            } else if let Some(k) = key.as_mut() {
+                k.push(c);
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                key = expecting_key.then(String::new);
+            }
+            '{' => {
+                stack.push(Segment::Key(None));
+                expecting_key = true;
+            }
+            '[' => stack.push(Segment::Index(0)),
+            '}' | ']' => {
+                stack.pop();
+            }
+            ',' => match stack.last_mut() {
+                Some(Segment::Index(i)) => *i += 1,
+                Some(Segment::Key(_)) => expecting_key = true,
+                None => {}
+            },
+            ':' => expecting_key = false,
+            _ => {}
+        }
+    }
+
+    let mut path = String::new();
+    for segment in stack {
+        match segment {
+            Segment::Key(Some(k)) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(&k);
+            }
+            Segment::Index(i) => path.push_str(&format!("[{i}]")),
+            Segment::Key(None) => {}
+        }
+    }
+    if path.is_empty() {
+        path.push('.');
+    }
+    path
+}
+
+/// The JSON within [`SNIPPET_RADIUS`] bytes of `offset`, marking any truncation with `...`
+fn snippet_around(json: &str, offset: usize) -> String {
+    let start = floor_char_boundary(json, offset.saturating_sub(SNIPPET_RADIUS));
+    let end = floor_char_boundary(json, (offset + SNIPPET_RADIUS).min(json.len()));
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    snippet.push_str(&json[start..end]);
+    if end < json.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonErrorContext;
+    use k8s_openapi::api::core::v1::Service;
+    use kube_core::watch::WatchEvent;
+
+    fn context<T: serde::de::DeserializeOwned + std::fmt::Debug>(json: &str) -> JsonErrorContext {
+        let err = serde_json::from_str::<T>(json).unwrap_err();
+        JsonErrorContext::new(json, &err)
+    }
+
+    #[test]
+    fn locates_invalid_type_in_watch_event() {
+        let json = r#"{"type":"ADDED","object":{"apiVersion":"v1","kind":"Service","metadata":{"name":"web"},"spec":{"ports":[{"port":80},{"port":"http"}]}}}"#;
+        let ctx = context::<WatchEvent<Service>>(json);
+        assert_eq!(ctx.path, "object.spec.ports[1].port");
+        assert!(ctx.snippet.starts_with("..."));
+        assert!(ctx.snippet.ends_with(r#"{"port":"http"}]}}}"#));
+    }
+
+    #[test]
+    fn truncates_long_snippets() {
+        let padding = "x".repeat(300);
+        let json = format!(r#"{{"a":"{padding}","b":{{"c":"\"quoted\"","d":é}},"e":"{padding}"}}"#);
+        let ctx = context::<serde_json::Value>(&json);
+        assert_eq!(ctx.path, "b.d");
+        assert!(ctx.snippet.starts_with("...") && ctx.snippet.ends_with("..."));
+        assert!(ctx.snippet.contains(r#""d":é"#));
+    }
+}
@@ -1,22 +1,24 @@
 //! Middleware types returned from `ConfigExt` methods.
-use tower::{filter::AsyncFilterLayer, util::Either, Layer};
+use tower::{util::Either, Layer};
 pub(crate) use tower_http::auth::AddAuthorizationLayer;
 
 mod base_uri;
 mod extra_headers;
+mod read_timeout;
+mod retry;
 
 pub use base_uri::{BaseUri, BaseUriLayer};
 pub use extra_headers::{ExtraHeaders, ExtraHeadersLayer};
+pub use read_timeout::{ReadTimeout, ReadTimeoutError, ReadTimeoutLayer, TimeoutBody};
+pub use retry::{Retry, RetryLayer};
 
-use super::auth::RefreshableToken;
+use super::auth::RefreshTokenLayer;
 /// Layer to set up `Authorization` header depending on the config.
-pub struct AuthLayer(pub(crate) Either<AddAuthorizationLayer, AsyncFilterLayer<RefreshableToken>>);
+pub struct AuthLayer(pub(crate) Either<AddAuthorizationLayer, RefreshTokenLayer>);
 
 impl<S> Layer<S> for AuthLayer {
-    type Service = Either<
-        <AddAuthorizationLayer as Layer<S>>::Service,
-        <AsyncFilterLayer<RefreshableToken> as Layer<S>>::Service,
-    >;
+    type Service =
+        Either<<AddAuthorizationLayer as Layer<S>>::Service, <RefreshTokenLayer as Layer<S>>::Service>;
 
     fn layer(&self, inner: S) -> Self::Service {
         self.0.layer(inner)
@@ -37,17 +39,19 @@ mod tests {
     use secrecy::SecretString;
     use tokio::sync::Mutex;
     use tokio_test::assert_ready_ok;
-    use tower::filter::AsyncFilterLayer;
     use tower_test::{mock, mock::Handle};
 
-    use crate::{client::AuthError, config::AuthInfo};
+    use crate::{
+        client::{auth::RefreshableToken, AuthError},
+        config::AuthInfo,
+    };
 
     #[tokio::test(flavor = "current_thread")]
     async fn valid_token() {
         const TOKEN: &str = "test";
         let auth = test_token(TOKEN.into());
         let (mut service, handle): (_, Handle<Request<hyper::Body>, Response<hyper::Body>>) =
-            mock::spawn_layer(AsyncFilterLayer::new(auth));
+            mock::spawn_layer(RefreshTokenLayer(auth));
 
         let spawned = tokio::spawn(async move {
             // Receive the requests and respond
@@ -73,7 +77,7 @@ mod tests {
         const TOKEN: &str = "\n";
         let auth = test_token(TOKEN.into());
         let (mut service, _handle) =
-            mock::spawn_layer::<Request<Body>, Response<Body>, _>(AsyncFilterLayer::new(auth));
+            mock::spawn_layer::<Request<Body>, Response<Body>, _>(RefreshTokenLayer(auth));
         let err = service
             .call(Request::builder().uri("/").body(Body::empty()).unwrap())
             .await
@@ -0,0 +1,249 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{HeaderMap, Request, Response};
+use http_body::Body;
+use pin_project::pin_project;
+use tokio::time::{sleep, Instant, Sleep};
+use tower::{BoxError, Layer, Service};
+
+/// Extra time given to a watch beyond its `timeoutSeconds` before considering the connection stale
+const WATCH_TIMEOUT_MARGIN: Duration = Duration::from_secs(5);
+/// How long a watch without `timeoutSeconds` may stay silent before considering the connection stale
+///
+/// The apiserver does not promise to send anything on a quiet watch, so this is generous,
+/// but still catches half-open connections that would otherwise hang forever.
+const WATCH_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Error returned when no data was read from the apiserver within the read timeout
+#[derive(Debug, thiserror::Error)]
+#[error("timed out reading from the apiserver after {0:?}")]
+pub struct ReadTimeoutError(pub Duration);
+
+#[derive(Clone)]
+/// Layer that fails requests when the apiserver stops sending data for too long
+///
+/// Watch requests are held open by the apiserver for up to their `timeoutSeconds`,
+/// so their read timeout is extended to cover that duration (or a generous idle timeout if it is unset).
+pub struct ReadTimeoutLayer {
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl<S> Layer<S> for ReadTimeoutLayer {
+    type Service = ReadTimeout<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReadTimeout {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Service that fails requests when the apiserver stops sending data for too long
+pub struct ReadTimeout<S> {
+    inner: S,
+    timeout: Option<Duration>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ReadTimeout<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Error: Into<BoxError>,
+    ResBody: Body<Data = Bytes>,
+    ResBody::Error: Into<BoxError>,
+{
+    type Error = BoxError;
+    type Future = ResponseFuture<S::Future>;
+    type Response = Response<TimeoutBody<ResBody>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let timeout = match watch_timeout(&req) {
+            Some(watch_timeout) => self.timeout.map(|timeout| {
+                // Watches without `timeoutSeconds` are held open for as long as the apiserver sees fit
+                timeout.max(watch_timeout.unwrap_or(WATCH_IDLE_TIMEOUT) + WATCH_TIMEOUT_MARGIN)
+            }),
+            None => self.timeout,
+        };
+        ResponseFuture {
+            inner: self.inner.call(req),
+            timeout,
+            sleep: timeout.map(|timeout| Box::pin(sleep(timeout))),
+        }
+    }
+}
+
+/// The `timeoutSeconds` of a watch request if set, or `None` if the request is not a watch
+fn watch_timeout<B>(req: &Request<B>) -> Option<Option<Duration>> {
+    let query = req.uri().query()?;
+    let pairs = query.split('&').filter_map(|pair| pair.split_once('='));
+    if !pairs.clone().any(|pair| pair == ("watch", "true")) {
+        return None;
+    }
+    Some(
+        pairs
+            .filter(|(key, _)| *key == "timeoutSeconds")
+            .find_map(|(_, value)| value.parse().ok())
+            .map(Duration::from_secs),
+    )
+}
+
+/// Future for [`ReadTimeout`], timing out while waiting for the response head
+#[pin_project]
+pub struct ResponseFuture<F> {
+    #[pin]
+    inner: F,
+    timeout: Option<Duration>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<F, E, B> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    E: Into<BoxError>,
+{
+    type Output = Result<Response<TimeoutBody<B>>, BoxError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Poll::Ready(res) = this.inner.poll(cx) {
+            let timeout = *this.timeout;
+            return Poll::Ready(
+                res.map(|res| res.map(|body| TimeoutBody::new(body, timeout)))
+                    .map_err(Into::into),
+            );
+        }
+        if let (Some(sleep), Some(timeout)) = (this.sleep.as_mut(), *this.timeout) {
+            if sleep.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(ReadTimeoutError(timeout).into()));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Response body for [`ReadTimeout`], timing out when no data arrives for too long
+#[pin_project]
+pub struct TimeoutBody<B> {
+    #[pin]
+    inner: B,
+    timeout: Option<Duration>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<B> TimeoutBody<B> {
+    fn new(inner: B, timeout: Option<Duration>) -> Self {
+        Self {
+            inner,
+            timeout,
+            sleep: timeout.map(|timeout| Box::pin(sleep(timeout))),
+        }
+    }
+}
+
+impl<B> Body for TimeoutBody<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        if let Poll::Ready(data) = this.inner.poll_data(cx) {
+            if let (Some(sleep), Some(timeout)) = (this.sleep.as_mut(), this.timeout) {
+                sleep.as_mut().reset(Instant::now() + *timeout);
+            }
+            return Poll::Ready(data.map(|data| data.map_err(Into::into)));
+        }
+        if let (Some(sleep), Some(timeout)) = (this.sleep.as_mut(), *this.timeout) {
+            if sleep.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Some(Err(ReadTimeoutError(timeout).into())));
+            }
+        }
+        Poll::Pending
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx).map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use hyper::body::HttpBody;
+    use tower::ServiceExt;
+    use tower_test::mock;
+
+    async fn respond_after(uri: &str, delay: Duration) -> Result<Option<Bytes>, BoxError> {
+        let (mock_service, handle) = mock::pair::<Request<hyper::Body>, Response<hyper::Body>>();
+        let mut service = ReadTimeoutLayer {
+            timeout: Some(Duration::from_secs(1)),
+        }
+        .layer(mock_service);
+        tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_request, send) = handle.next_request().await.expect("service not called");
+            let (mut sender, body) = hyper::Body::channel();
+            send.send_response(Response::new(body));
+            tokio::time::sleep(delay).await;
+            sender.send_data(Bytes::from_static(b"{}")).await.ok();
+        });
+        let req = Request::get(uri).body(hyper::Body::empty()).unwrap();
+        let mut res = service.ready().await?.call(req).await?;
+        res.body_mut().data().await.transpose()
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn unary_request_times_out() {
+        let err = respond_after("/api/v1/pods?", Duration::from_secs(3))
+            .await
+            .unwrap_err();
+        assert!(err.is::<ReadTimeoutError>());
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn watch_request_outlives_read_timeout() {
+        let uri = "/api/v1/pods?&watch=true&resourceVersion=0&timeoutSeconds=10";
+        let data = respond_after(uri, Duration::from_secs(3)).await.unwrap();
+        assert_eq!(data.unwrap(), Bytes::from_static(b"{}"));
+
+        let err = respond_after(uri, Duration::from_secs(20)).await.unwrap_err();
+        assert!(err.is::<ReadTimeoutError>());
+
+        let uri = "/api/v1/pods?&watch=true&resourceVersion=0";
+        let data = respond_after(uri, Duration::from_secs(300)).await.unwrap();
+        assert_eq!(data.unwrap(), Bytes::from_static(b"{}"));
+
+        let err = respond_after(uri, Duration::from_secs(3600)).await.unwrap_err();
+        assert!(err.is::<ReadTimeoutError>());
+    }
+}
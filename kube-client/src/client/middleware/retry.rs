@@ -0,0 +1,305 @@
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use http::{header::RETRY_AFTER, HeaderMap, Method, Request, Response, StatusCode};
+use http_body::Body;
+use tower::{BoxError, Layer, Service, ServiceExt};
+
+#[derive(Clone, Debug)]
+/// Layer that retries idempotent requests failing with transient errors
+///
+/// Requests are retried when the apiserver responds with `429`, `500`, `502`, `503` or `504`,
+/// or when the connection fails, waiting for the `Retry-After` header when present
+/// and backing off exponentially otherwise.
+///
+/// Only `GET`, `HEAD` and `OPTIONS` requests without a body are retried, which covers gets, lists
+/// and establishing watches. Writes are never retried, since they may have been applied already.
+///
+/// Retries are disabled by default. The wrapped service must be `Clone`,
+/// so put a [`BufferLayer`](tower::buffer::BufferLayer) in front of the default stack:
+///
+/// ```no_run
+/// use kube::{client::{middleware::RetryLayer, ClientBuilder}, Client, Config};
+/// use std::time::Duration;
+/// use tower::buffer::BufferLayer;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = Config::infer().await?;
+///     let client: Client = ClientBuilder::try_from(config)?
+///         .with_layer(&BufferLayer::new(1024))
+///         .with_layer(&RetryLayer::default().max_attempts(5).max_backoff(Duration::from_secs(5)))
+///         .build();
+///     Ok(())
+/// }
+/// ```
+pub struct RetryLayer {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryLayer {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryLayer {
+    /// Sets the maximum number of attempts per request, including the first one
+    #[must_use]
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the backoff before the first retry, which doubles for every further retry
+    #[must_use]
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum backoff between attempts, also capping any `Retry-After`
+    #[must_use]
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = Retry<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry {
+            inner,
+            policy: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Service that retries idempotent requests failing with transient errors
+pub struct Retry<S> {
+    inner: S,
+    policy: RetryLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Retry<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    ReqBody: Body + Default + Send + 'static,
+    ResBody: Send + 'static,
+{
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = Response<ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        // Keep the service that was polled ready for the first attempt
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let mut attempt = 1;
+            loop {
+                let retry = (attempt < policy.max_attempts).then(|| try_clone(&req)).flatten();
+                let res = inner
+                    .ready()
+                    .await
+                    .map_err(Into::into)?
+                    .call(req)
+                    .await
+                    .map_err(Into::into);
+                let next = match retry {
+                    Some(next) => next,
+                    None => return res,
+                };
+                let delay = match res {
+                    Ok(res) if is_transient(res.status()) => {
+                        retry_after(res.headers()).unwrap_or_else(|| policy.backoff(attempt))
+                    }
+                    Ok(res) => return Ok(res),
+                    Err(err) if is_connection_error(&err) => policy.backoff(attempt),
+                    Err(err) => return Err(err),
+                };
+                let delay = delay.min(policy.max_backoff);
+                tracing::debug!(attempt, ?delay, "retrying request");
+                tokio::time::sleep(delay).await;
+                req = next;
+                attempt += 1;
+            }
+        })
+    }
+}
+
+/// A copy of `req` to retry with, if it is safe to send again
+fn try_clone<B: Body + Default>(req: &Request<B>) -> Option<Request<B>> {
+    let idempotent = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if !idempotent || req.body().size_hint().exact() != Some(0) {
+        return None;
+    }
+    let mut clone = Request::new(B::default());
+    *clone.method_mut() = req.method().clone();
+    *clone.uri_mut() = req.uri().clone();
+    *clone.version_mut() = req.version();
+    *clone.headers_mut() = req.headers().clone();
+    // Extensions cannot be cloned, but keep the operation name used for tracing
+    if let Some(name) = req.extensions().get::<&'static str>() {
+        clone.extensions_mut().insert(*name);
+    }
+    Some(clone)
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// The delay requested by a `Retry-After` header in seconds (the apiserver never sends dates)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers.get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+fn is_connection_error(err: &BoxError) -> bool {
+    use std::io::ErrorKind;
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&**err);
+    while let Some(cause) = source {
+        if matches!(cause.downcast_ref::<hyper::Error>(), Some(e) if e.is_connect()) {
+            return true;
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            if matches!(
+                e.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::BrokenPipe
+            ) {
+                return true;
+            }
+        }
+        source = cause.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use hyper::Body;
+    use tower_test::mock;
+
+    fn respond(status: u16) -> Response<Body> {
+        Response::builder().status(status).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn retries_transient_errors_on_reads() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let mut service = RetryLayer::default().layer(mock_service);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(respond(503));
+            let (request, send) = handle.next_request().await.expect("request not retried");
+            assert_eq!(request.uri(), "/api/v1/pods");
+            assert_eq!(request.extensions().get::<&'static str>(), Some(&"list"));
+            send.send_response(
+                Response::builder()
+                    .status(429)
+                    .header(RETRY_AFTER, "1")
+                    .body(Body::empty())
+                    .unwrap(),
+            );
+            let (_, send) = handle.next_request().await.expect("request not retried");
+            send.send_response(respond(200));
+        });
+
+        let mut req = Request::get("/api/v1/pods").body(Body::empty()).unwrap();
+        req.extensions_mut().insert("list");
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn gives_up_after_max_attempts() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let mut service = RetryLayer::default().max_attempts(2).layer(mock_service);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for _ in 0..2 {
+                let (_, send) = handle.next_request().await.expect("service not called");
+                send.send_response(respond(500));
+            }
+        });
+
+        let req = Request::get("/api/v1/pods").body(Body::empty()).unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn does_not_retry_writes_or_client_errors() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let mut service = RetryLayer::default().layer(mock_service);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(respond(503));
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(respond(404));
+        });
+
+        let req = Request::post("/api/v1/namespaces/ns/pods")
+            .body(Body::from("{}"))
+            .unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let req = Request::get("/api/v1/namespaces/ns/pods/missing")
+            .body(Body::empty())
+            .unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = RetryLayer::default()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(5));
+        let backoffs = (1..=4).map(|attempt| policy.backoff(attempt)).collect::<Vec<_>>();
+        assert_eq!(backoffs, [1, 2, 4, 5].map(Duration::from_secs));
+    }
+}
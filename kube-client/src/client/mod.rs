@@ -32,7 +32,9 @@ mod body;
 mod builder;
 // Add `into_stream()` to `http::Body`
 use body::BodyStreamExt;
+use middleware::ReadTimeoutError;
 mod config_ext;
+mod json_context;
 pub use auth::Error as AuthError;
 pub use config_ext::ConfigExt;
 pub mod middleware;
@@ -49,9 +51,13 @@ pub use tls::openssl_tls::Error as OpensslTlsError;
 #[cfg_attr(docsrs, doc(cfg(feature = "oauth")))]
 pub use auth::OAuthError;
 
+#[cfg(feature = "oidc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oidc")))]
+pub use auth::OidcError;
+
 #[cfg(feature = "ws")] pub use upgrade::UpgradeConnectionError;
 
-pub use builder::{ClientBuilder, DynBody};
+pub use builder::{ClientBuilder, DynBody, DEFAULT_USER_AGENT};
 
 /// Client for connecting with a Kubernetes cluster.
 ///
@@ -147,9 +153,11 @@ impl Client {
                 if err.is::<Error>() {
                     // Error decorating request
                     *err.downcast::<Error>().expect("kube_client::Error")
+                } else if err.is::<ReadTimeoutError>() {
+                    Error::Timeout(err)
                 } else if err.is::<hyper::Error>() {
                     // Error requesting
-                    Error::HyperError(*err.downcast::<hyper::Error>().expect("hyper::Error"))
+                    hyper_error(*err.downcast::<hyper::Error>().expect("hyper::Error"))
                 } else {
                     // Errors from other middlewares
                     Error::Service(err)
@@ -227,7 +235,7 @@ impl Client {
         // trace!("Status = {:?} for {}", status, res.url());
         let body_bytes = hyper::body::to_bytes(res.into_body())
             .await
-            .map_err(Error::HyperError)?;
+            .map_err(hyper_error)?;
         let text = String::from_utf8(body_bytes.to_vec()).map_err(Error::FromUtf8)?;
         handle_api_errors(&text, status)?;
 
@@ -242,7 +250,7 @@ impl Client {
     ) -> Result<impl Stream<Item = Result<Bytes>>> {
         let res = self.send(request.map(Body::from)).await?;
         // trace!("Status = {:?} for {}", res.status(), res.url());
-        Ok(res.into_body().map_err(Error::HyperError))
+        Ok(res.into_body().map_err(hyper_error))
     }
 
     /// Perform a raw HTTP request against the API and get back either an object
@@ -311,6 +319,9 @@ impl Client {
                             return Some(Err(Error::Api(e_resp)));
                         }
                         // Parsing error
+                        let context = json_context::JsonErrorContext::new(&line, &e);
+                        tracing::warn!("failed to deserialize watch event {}: {}", context, e);
+                        tracing::debug!("undeserializable watch event: {}", line);
                         Some(Err(Error::SerdeError(e)))
                     }
                 },
@@ -442,6 +453,7 @@ fn handle_api_errors(text: &str, s: StatusCode) -> Result<()> {
                 code: s.as_u16(),
                 message: format!("{:?}", text),
                 reason: "Failed to parse error data".into(),
+                details: None,
             };
             tracing::debug!("Unsuccessful: {:?} (reconstruct)", ae);
             Err(Error::Api(ae))
@@ -460,6 +472,21 @@ impl TryFrom<Config> for Client {
     }
 }
 
+// Timeouts surface from `hyper-timeout` as `io::ErrorKind::TimedOut`, and from the response body as a
+// `ReadTimeoutError`, somewhere in the error chain
+fn hyper_error(err: hyper::Error) -> Error {
+    let mut source = std::error::Error::source(&err);
+    while let Some(cause) = source {
+        let timed_out = cause.is::<ReadTimeoutError>()
+            || matches!(cause.downcast_ref::<std::io::Error>(), Some(e) if e.kind() == std::io::ErrorKind::TimedOut);
+        if timed_out {
+            return Error::Timeout(err.into());
+        }
+        source = cause.source();
+    }
+    Error::HyperError(err)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Api, Client};
@@ -503,4 +530,135 @@ mod tests {
         assert_eq!(pod.metadata.annotations.unwrap().get("kube-rs").unwrap(), "test");
         spawned.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn api_errors_carry_status_details() {
+        use crate::{core::params::PostParams, Error};
+        use http::StatusCode;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let status = serde_json::json!({
+                "kind": "Status",
+                "apiVersion": "v1",
+                "status": "Failure",
+                "message": "Pod \"test\" is invalid: spec.containers: Required value",
+                "reason": "Invalid",
+                "details": {
+                    "name": "test",
+                    "kind": "Pod",
+                    "causes": [{
+                        "reason": "FieldValueRequired",
+                        "message": "Required value",
+                        "field": "spec.containers"
+                    }]
+                },
+                "code": 422
+            });
+            send.send_response(
+                Response::builder()
+                    .status(StatusCode::UNPROCESSABLE_ENTITY)
+                    .body(Body::from(serde_json::to_vec(&status).unwrap()))
+                    .unwrap(),
+            );
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(
+                Response::builder()
+                    .status(StatusCode::BAD_GATEWAY)
+                    .body(Body::from("upstream unavailable"))
+                    .unwrap(),
+            );
+        });
+
+        let pods: Api<Pod> = Api::default_namespaced(Client::new(mock_service, "default"));
+        match pods.create(&PostParams::default(), &Pod::default()).await {
+            Err(Error::Api(err)) => {
+                assert_eq!(err.code, 422);
+                assert_eq!(err.reason, "Invalid");
+                let details = err.details.expect("details should be parsed");
+                assert_eq!(details.name, "test");
+                assert_eq!(details.causes[0].field, "spec.containers");
+            }
+            res => panic!("expected an api error, got {:?}", res),
+        }
+        match pods.get("test").await {
+            Err(Error::Api(err)) => {
+                assert_eq!(err.code, 502);
+                assert!(err.message.contains("upstream unavailable"));
+                assert!(err.details.is_none());
+            }
+            res => panic!("expected an api error, got {:?}", res),
+        }
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_map_request() {
+        use crate::client::ClientBuilder;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers().get("x-tenant-id").unwrap(), "blue");
+            send.send_response(Response::builder().body(Body::from("{}")).unwrap());
+        });
+
+        let client = ClientBuilder::new(mock_service, "default")
+            .map_request(|mut req: Request<Body>| {
+                req.headers_mut().insert("x-tenant-id", "blue".parse().unwrap());
+                req
+            })
+            .build();
+        let req = Request::get("/version").body(vec![]).unwrap();
+        client.request_text(req).await.unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        use crate::client::ClientBuilder;
+        use http::{header::USER_AGENT, HeaderValue};
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers().get(USER_AGENT).unwrap(), "my-controller/1.0");
+            send.send_response(Response::builder().body(Body::from("{}")).unwrap());
+        });
+
+        let client = ClientBuilder::new(mock_service, "default")
+            .user_agent(HeaderValue::from_static("my-controller/1.0"))
+            .build();
+        let req = Request::get("/version").body(vec![]).unwrap();
+        client.request_text(req).await.unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn read_timeout_is_reported_as_timeout() {
+        use crate::{Config, Error};
+        use std::time::Duration;
+
+        // Accept connections but never respond
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut conns = vec![];
+            loop {
+                let (conn, _) = listener.accept().await.unwrap();
+                conns.push(conn);
+            }
+        });
+
+        let mut config = Config::new(format!("http://{addr}").parse().unwrap());
+        config.read_timeout = Some(Duration::from_millis(100));
+        let pods: Api<Pod> = Api::default_namespaced(Client::try_from(config).unwrap());
+        let err = pods.get("test").await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "unexpected error: {err:?}");
+        server.abort();
+    }
 }
@@ -25,11 +25,14 @@ pub struct Kubeconfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preferences: Option<Preferences>,
     /// Referencable names to cluster configs
+    #[serde(default)]
     pub clusters: Vec<NamedCluster>,
     /// Referencable names to user configs
     #[serde(rename = "users")]
+    #[serde(default)]
     pub auth_infos: Vec<NamedAuthInfo>,
     /// Referencable names to context configs
+    #[serde(default)]
     pub contexts: Vec<NamedContext>,
     /// The name of the context that you would like to use by default
     #[serde(rename = "current-context")]
@@ -195,6 +198,10 @@ pub struct AuthInfo {
     #[serde(rename = "as-groups")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub impersonate_groups: Option<Vec<String>>,
+    /// The uid to impersonate.
+    #[serde(rename = "as-uid")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonate_uid: Option<String>,
 
     /// Specifies a custom authentication plugin for the kubernetes cluster.
     #[serde(rename = "auth-provider")]
@@ -353,16 +360,28 @@ impl Kubeconfig {
                     return Ok(None);
                 }
 
-                let merged = paths.iter().try_fold(Kubeconfig::default(), |m, p| {
-                    Kubeconfig::read_from(p).and_then(|c| m.merge(c))
-                })?;
-                Ok(Some(merged))
+                Self::read_from_paths(&paths).map(Some)
             }
 
             None => Ok(None),
         }
     }
 
+    // Merges the files in order, skipping files that do not exist like kubectl does
+    fn read_from_paths(paths: &[PathBuf]) -> Result<Self, KubeconfigError> {
+        paths.iter().try_fold(
+            Kubeconfig::default(),
+            |merged, path| match Kubeconfig::read_from(path) {
+                Ok(config) => merged.merge(config),
+                Err(KubeconfigError::ReadConfig(err, _)) if err.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::debug!(?path, "skipping missing kubeconfig");
+                    Ok(merged)
+                }
+                Err(err) => Err(err),
+            },
+        )
+    }
+
     /// Merge kubeconfig file according to the rules described in
     /// <https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/#merging-kubeconfig-files>
     ///
@@ -688,6 +707,52 @@ users:
         Ok(())
     }
 
+    #[test]
+    fn kubeconfig_paths_merge_first_wins_and_skip_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("clusters");
+        std::fs::write(
+            &first,
+            r#"
+current-context: dev
+clusters:
+- name: dev
+  cluster:
+    server: https://dev.example.com
+contexts:
+- name: dev
+  context:
+    cluster: dev
+    user: dev
+"#,
+        )
+        .unwrap();
+        let second = dir.path().join("users");
+        std::fs::write(
+            &second,
+            r#"
+current-context: prod
+clusters:
+- name: dev
+  cluster:
+    server: https://shadowed.example.com
+users:
+- name: dev
+  user:
+    token: abc
+"#,
+        )
+        .unwrap();
+        let missing = dir.path().join("missing");
+
+        let cfg = Kubeconfig::read_from_paths(&[first, missing, second]).unwrap();
+        assert_eq!(cfg.current_context.as_deref(), Some("dev"));
+        assert_eq!(cfg.clusters.len(), 1);
+        assert_eq!(cfg.clusters[0].cluster.server, "https://dev.example.com");
+        assert_eq!(cfg.auth_infos[0].name, "dev");
+        assert_eq!(cfg.contexts[0].name, "dev");
+    }
+
     #[test]
     fn kubeconfig_from_empty_string() {
         let cfg = Kubeconfig::from_yaml("").unwrap();
@@ -709,7 +774,7 @@ password: kube_rs
         token: None, token_file: None, client_certificate: None, \
         client_certificate_data: None, client_key: None, \
         client_key_data: None, impersonate: None, \
-        impersonate_groups: None, \
+        impersonate_groups: None, impersonate_uid: None, \
         auth_provider: None, \
         exec: None \
         }";
@@ -134,6 +134,8 @@ pub struct Config {
     pub connect_timeout: Option<std::time::Duration>,
     /// Set the timeout for the Kubernetes API response.
     ///
+    /// Watches are extended to stay open for their [`ListParams::timeout`](crate::api::ListParams::timeout) when that is longer.
+    ///
     /// A value of `None` means no timeout
     pub read_timeout: Option<std::time::Duration>,
     /// Set the timeout for the Kubernetes API request.
@@ -155,6 +157,13 @@ pub struct Config {
     // TODO Actually support proxy or create an example with custom client
     /// Optional proxy URL.
     pub proxy_url: Option<http::Uri>,
+    /// Whether to disable compression of responses
+    ///
+    /// When set, every request asks for `Accept-Encoding: identity` and responses are not decompressed.
+    /// This is intended as a debugging aid for inspecting plaintext traffic (e.g. through `mitmproxy`).
+    /// Without the `gzip` feature, compression is never requested in the first place,
+    /// so the header only restates the default.
+    pub disable_compression: bool,
 }
 
 impl Config {
@@ -176,6 +185,7 @@ impl Config {
             accept_invalid_certs: false,
             auth_info: AuthInfo::default(),
             proxy_url: None,
+            disable_compression: false,
         }
     }
 
@@ -231,6 +241,7 @@ impl Config {
                 ..Default::default()
             },
             proxy_url: None,
+            disable_compression: false,
         })
     }
 
@@ -255,6 +266,20 @@ impl Config {
         Self::new_from_loader(loader).await
     }
 
+    /// Create configuration from raw kubeconfig YAML
+    ///
+    /// This is useful when the kubeconfig is handed over as a string, e.g. from an environment variable
+    /// or a secret, and avoids writing it to a temporary file first.
+    /// The named `context` is selected when given, otherwise the kubeconfig's current-context is used.
+    pub async fn from_kubeconfig_str(yaml: &str, context: Option<&str>) -> Result<Self, KubeconfigError> {
+        let kubeconfig = Kubeconfig::from_yaml(yaml)?;
+        let options = KubeConfigOptions {
+            context: context.map(String::from),
+            ..KubeConfigOptions::default()
+        };
+        Self::from_custom_kubeconfig(kubeconfig, &options).await
+    }
+
     async fn new_from_loader(loader: ConfigLoader) -> Result<Self, KubeconfigError> {
         let cluster_url = loader
             .cluster
@@ -290,6 +315,7 @@ impl Config {
             accept_invalid_certs,
             proxy_url: loader.proxy_url()?,
             auth_info: loader.user,
+            disable_compression: false,
         })
     }
 
@@ -337,7 +363,7 @@ impl Config {
     }
 }
 
-fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
+pub(crate) fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
     Ok(pem::parse_many(data)?
         .into_iter()
         .filter_map(|p| {
@@ -412,4 +438,42 @@ mod tests {
         let kubeconfig = Config::infer().await.unwrap();
         assert_eq!(kubeconfig.cluster_url, "https://0.0.0.0:6443/");
     }
+
+    #[tokio::test]
+    async fn config_from_kubeconfig_str_selects_context() {
+        use super::Config;
+        let cfgraw = r#"
+        apiVersion: v1
+        clusters:
+        - cluster:
+            server: https://0.0.0.0:6443
+          name: first
+        - cluster:
+            server: https://1.1.1.1:6443
+          name: second
+        contexts:
+        - context:
+            cluster: first
+            user: admin
+          name: first
+        - context:
+            cluster: second
+            user: admin
+            namespace: apps
+          name: second
+        current-context: first
+        kind: Config
+        users:
+        - name: admin
+          user:
+            token: hunter2
+        "#;
+        let config = Config::from_kubeconfig_str(cfgraw, None).await.unwrap();
+        assert_eq!(config.cluster_url, "https://0.0.0.0:6443/");
+        assert_eq!(config.default_namespace, "default");
+
+        let config = Config::from_kubeconfig_str(cfgraw, Some("second")).await.unwrap();
+        assert_eq!(config.cluster_url, "https://1.1.1.1:6443/");
+        assert_eq!(config.default_namespace, "apps");
+    }
 }
@@ -0,0 +1,127 @@
+use crate::{
+    api::{Api, DynamicObject, ListParams, ObjectList},
+    Client, Error,
+};
+use kube_core::discovery::ApiResource;
+
+/// The outcome of listing several resources, where each resource may fail independently
+///
+/// Returned by [`list_resources`] and [`Discovery::list_all`](super::Discovery::list_all).
+#[derive(Debug, Default)]
+pub struct PartialList {
+    /// Lists of the resources that could be listed, in the order they were requested
+    pub lists: Vec<(ApiResource, ObjectList<DynamicObject>)>,
+    /// Errors for the resources that could not be listed, in the order they were requested
+    pub errors: Vec<(ApiResource, Error)>,
+}
+
+impl PartialList {
+    /// Whether every resource was listed successfully
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterate over every object that was listed, alongside its resource
+    pub fn objects(&self) -> impl Iterator<Item = (&ApiResource, &DynamicObject)> {
+        self.lists
+            .iter()
+            .flat_map(|(ar, list)| list.items.iter().map(move |obj| (ar, obj)))
+    }
+}
+
+/// List several resources across all namespaces, collecting failures per resource
+///
+/// Unlike calling [`Api::list`] in a loop with `?`, a resource that cannot be listed
+/// (e.g. because the aggregated apiserver serving it is unavailable) is recorded in
+/// [`PartialList::errors`] rather than aborting the remaining lists.
+///
+/// ```no_run
+/// use kube::{Client, api::{ApiResource, ListParams}, discovery};
+/// use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let resources = [ApiResource::erase::<ConfigMap>(&()), ApiResource::erase::<Secret>(&())];
+///     let res = discovery::list_resources(&client, resources, &ListParams::default()).await;
+///     for (ar, err) in &res.errors {
+///         eprintln!("failed to list {}: {}", ar.plural, err);
+///     }
+///     println!("found {} objects", res.objects().count());
+///     Ok(())
+/// }
+/// ```
+pub async fn list_resources(
+    client: &Client,
+    resources: impl IntoIterator<Item = ApiResource>,
+    lp: &ListParams,
+) -> PartialList {
+    let mut res = PartialList::default();
+    for ar in resources {
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
+        match api.list(lp).await {
+            Ok(list) => res.lists.push((ar, list)),
+            Err(err) => res.errors.push((ar, err)),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::list_resources;
+    use crate::{api::ListParams, Client, Error};
+    use futures::pin_mut;
+    use http::{Request, Response};
+    use hyper::Body;
+    use k8s_openapi::api::core::v1 as corev1;
+    use kube_core::discovery::ApiResource;
+    use tower_test::mock;
+
+    #[tokio::test]
+    async fn list_resources_collects_errors_per_resource() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/configmaps");
+            let status = serde_json::json!({
+                "status": "Failure",
+                "message": "the server is currently unable to handle the request",
+                "reason": "ServiceUnavailable",
+                "code": 503,
+            });
+            send.send_response(
+                Response::builder()
+                    .status(503)
+                    .body(Body::from(status.to_string()))
+                    .unwrap(),
+            );
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/secrets");
+            let list = serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "SecretList",
+                "metadata": { "resourceVersion": "1" },
+                "items": [{ "apiVersion": "v1", "kind": "Secret", "metadata": { "name": "token" } }],
+            });
+            send.send_response(Response::builder().body(Body::from(list.to_string())).unwrap());
+        });
+
+        let resources = [
+            ApiResource::erase::<corev1::ConfigMap>(&()),
+            ApiResource::erase::<corev1::Secret>(&()),
+        ];
+        let client = Client::new(mock_service, "default");
+        let res = list_resources(&client, resources, &ListParams::default()).await;
+        assert!(!res.is_complete());
+        assert_eq!(res.errors.len(), 1);
+        assert_eq!(res.errors[0].0.kind, "ConfigMap");
+        assert!(matches!(&res.errors[0].1, Error::Api(err) if err.code == 503));
+        let objects = res.objects().collect::<Vec<_>>();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].0.kind, "Secret");
+        assert_eq!(objects[0].1.metadata.name.as_deref(), Some("token"));
+        spawned.await.unwrap();
+    }
+}
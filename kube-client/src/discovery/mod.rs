@@ -1,12 +1,14 @@
 //! High-level utilities for runtime API discovery.
 
-use crate::{Client, Result};
+use crate::{api::ListParams, Client, Result};
 pub use kube_core::discovery::{verbs, ApiCapabilities, ApiResource, Scope};
 use kube_core::gvk::GroupVersionKind;
 use std::collections::HashMap;
 mod apigroup;
 pub mod oneshot;
 pub use apigroup::ApiGroup;
+mod list;
+pub use list::{list_resources, PartialList};
 mod parse;
 
 // re-export one-shots
@@ -167,4 +169,48 @@ impl Discovery {
             .into_iter()
             .find(|res| res.0.kind == gvk.kind)
     }
+
+    /// Finds the recommended [`ApiResource`] and its [`ApiCapabilities`] for a kind or plural name
+    ///
+    /// Accepts the same loose names as `kubectl get`, e.g. `Deployment`, `deployment` or `deployments`.
+    /// When several groups serve a matching resource, the core group wins, then groups in alphabetical order.
+    ///
+    /// ```no_run
+    /// use kube::{Client, api::{Api, DynamicObject}, discovery::Discovery, ResourceExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::new(client.clone()).run().await?;
+    ///     let (ar, _caps) = discovery.resolve_kind("pods").unwrap();
+    ///     let api: Api<DynamicObject> = Api::all_with(client, &ar);
+    ///     for pod in api.list(&Default::default()).await? {
+    ///         println!("Found pod: {}", pod.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resolve_kind(&self, name: &str) -> Option<(ApiResource, ApiCapabilities)> {
+        let name = name.to_lowercase();
+        self.groups_alphabetical()
+            .into_iter()
+            .flat_map(ApiGroup::recommended_resources)
+            .find(|(ar, _)| ar.kind.to_lowercase() == name || ar.plural == name)
+    }
+
+    /// Lists every discovered resource that supports listing, across all namespaces
+    ///
+    /// Only the recommended version of each resource is listed. Resources that fail to list
+    /// are reported in [`PartialList::errors`] instead of aborting the remaining lists,
+    /// so that one unavailable aggregated apiserver does not hide every other resource.
+    /// See [`list_resources`] for details.
+    pub async fn list_all(&self, lp: &ListParams) -> PartialList {
+        let resources = self
+            .groups_alphabetical()
+            .into_iter()
+            .flat_map(ApiGroup::recommended_resources)
+            .filter(|(_, caps)| caps.supports_operation(verbs::LIST))
+            .map(|(ar, _)| ar)
+            .collect::<Vec<_>>();
+        list_resources(&self.client, resources, lp).await
+    }
 }
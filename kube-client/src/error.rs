@@ -13,6 +13,9 @@ pub enum Error {
     /// It's also used in `WatchEvent` from watch calls.
     ///
     /// It's quite common to get a `410 Gone` when the `resourceVersion` is too old.
+    ///
+    /// When the apiserver explains the failure further (such as which fields failed validation),
+    /// that is available in [`ErrorResponse::details`].
     #[error("ApiError: {0} ({0:?})")]
     Api(#[source] ErrorResponse),
 
@@ -20,6 +23,10 @@ pub enum Error {
     #[cfg(feature = "client")]
     #[error("HyperError: {0}")]
     HyperError(#[source] hyper::Error),
+    /// A request exceeded the connect, read or write timeout of the [`Config`](crate::Config)
+    #[cfg(feature = "client")]
+    #[error("Request timed out: {0}")]
+    Timeout(#[source] tower::BoxError),
     /// Service error
     #[cfg(feature = "client")]
     #[error("ServiceError: {0}")]
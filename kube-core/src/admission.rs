@@ -334,6 +334,16 @@ impl AdmissionResponse {
         Ok(self)
     }
 
+    /// Add the JSON patch that turns `original` into `mutated` to the response.
+    ///
+    /// This is a convenience over [`AdmissionResponse::with_patch`] for mutating webhooks
+    /// that modify a copy of the object from the request rather than building patches by hand.
+    pub fn with_mutation<T: Serialize>(self, original: &T, mutated: &T) -> Result<Self, SerializePatchError> {
+        let original = serde_json::to_value(original).map_err(SerializePatchError)?;
+        let mutated = serde_json::to_value(mutated).map_err(SerializePatchError)?;
+        self.with_patch(json_patch::diff(&original, &mutated))
+    }
+
     /// Converts an [`AdmissionResponse`] into a generic [`AdmissionReview`] that
     /// can be used as a webhook response.
     pub fn into_review(self) -> AdmissionReview<DynamicObject> {
@@ -358,7 +368,9 @@ mod test {
     const WEBHOOK_BODY: &str = r#"{"kind":"AdmissionReview","apiVersion":"admission.k8s.io/v1","request":{"uid":"0c9a8d74-9cb7-44dd-b98e-09fd62def2f4","kind":{"group":"","version":"v1","kind":"Pod"},"resource":{"group":"","version":"v1","resource":"pods"},"requestKind":{"group":"","version":"v1","kind":"Pod"},"requestResource":{"group":"","version":"v1","resource":"pods"},"name":"echo-pod","namespace":"colin-coder","operation":"CREATE","userInfo":{"username":"colin@coder.com","groups":["system:authenticated"],"extra":{"iam.gke.io/user-assertion":["REDACTED"],"user-assertion.cloud.google.com":["REDACTED"]}},"object":{"kind":"Pod","apiVersion":"v1","metadata":{"name":"echo-pod","namespace":"colin-coder","creationTimestamp":null,"labels":{"app":"echo-server"},"annotations":{"kubectl.kubernetes.io/last-applied-configuration":"{\"apiVersion\":\"v1\",\"kind\":\"Pod\",\"metadata\":{\"annotations\":{},\"labels\":{\"app\":\"echo-server\"},\"name\":\"echo-pod\",\"namespace\":\"colin-coder\"},\"spec\":{\"containers\":[{\"image\":\"jmalloc/echo-server\",\"name\":\"echo-server\",\"ports\":[{\"containerPort\":8080,\"name\":\"http-port\"}]}]}}\n"},"managedFields":[{"manager":"kubectl","operation":"Update","apiVersion":"v1","time":"2021-03-29T23:02:16Z","fieldsType":"FieldsV1","fieldsV1":{"f:metadata":{"f:annotations":{".":{},"f:kubectl.kubernetes.io/last-applied-configuration":{}},"f:labels":{".":{},"f:app":{}}},"f:spec":{"f:containers":{"k:{\"name\":\"echo-server\"}":{".":{},"f:image":{},"f:imagePullPolicy":{},"f:name":{},"f:ports":{".":{},"k:{\"containerPort\":8080,\"protocol\":\"TCP\"}":{".":{},"f:containerPort":{},"f:name":{},"f:protocol":{}}},"f:resources":{},"f:terminationMessagePath":{},"f:terminationMessagePolicy":{}}},"f:dnsPolicy":{},"f:enableServiceLinks":{},"f:restartPolicy":{},"f:schedulerName":{},"f:securityContext":{},"f:terminationGracePeriodSeconds":{}}}}]},"spec":{"volumes":[{"name":"default-token-rxbqq","secret":{"secretName":"default-token-rxbqq"}}],"containers":[{"name":"echo-server","image":"jmalloc/echo-server","ports":[{"name":"http-port","containerPort":8080,"protocol":"TCP"}],"resources":{},"volumeMounts":[{"name":"default-token-rxbqq","readOnly":true,"mountPath":"/var/run/secrets/kubernetes.io/serviceaccount"}],"terminationMessagePath":"/dev/termination-log","terminationMessagePolicy":"File","imagePullPolicy":"Always"}],"restartPolicy":"Always","terminationGracePeriodSeconds":30,"dnsPolicy":"ClusterFirst","serviceAccountName":"default","serviceAccount":"default","securityContext":{},"schedulerName":"default-scheduler","tolerations":[{"key":"node.kubernetes.io/not-ready","operator":"Exists","effect":"NoExecute","tolerationSeconds":300},{"key":"node.kubernetes.io/unreachable","operator":"Exists","effect":"NoExecute","tolerationSeconds":300}],"priority":0,"enableServiceLinks":true},"status":{}},"oldObject":null,"dryRun":false,"options":{"kind":"CreateOptions","apiVersion":"meta.k8s.io/v1"}}}"#;
 
     use crate::{
-        admission::{AdmissionResponse, AdmissionReview, ConvertAdmissionReviewError},
+        admission::{
+            AdmissionRequest, AdmissionResponse, AdmissionReview, ConvertAdmissionReviewError, PatchType,
+        },
         DynamicObject,
     };
 
@@ -380,4 +392,28 @@ mod test {
         assert_eq!(&rev_typ, &res.types);
         Ok(())
     }
+
+    #[test]
+    fn mutation_computes_json_patch() -> Result<(), Box<dyn std::error::Error>> {
+        let rev = serde_json::from_str::<AdmissionReview<DynamicObject>>(WEBHOOK_BODY).unwrap();
+        let req: AdmissionRequest<DynamicObject> = rev.try_into()?;
+        let original = req.object.clone().unwrap();
+        let mut mutated = original.clone();
+        mutated
+            .metadata
+            .labels
+            .as_mut()
+            .unwrap()
+            .insert("team".into(), "echo".into());
+
+        let res = AdmissionResponse::from(&req).with_mutation(&original, &mutated)?;
+        assert!(res.allowed);
+        assert_eq!(res.patch_type, Some(PatchType::JsonPatch));
+        let patch: serde_json::Value = serde_json::from_slice(&res.patch.unwrap())?;
+        assert_eq!(
+            patch,
+            serde_json::json!([{"op": "add", "path": "/metadata/labels/team", "value": "echo"}])
+        );
+        Ok(())
+    }
 }
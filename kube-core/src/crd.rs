@@ -11,6 +11,19 @@ pub mod v1 {
         ///
         /// This is using the stable v1::CustomResourceDefinitions (present in kubernetes >= 1.16)
         fn crd() -> Crd;
+        /// Helper to return the `openAPIV3Schema` of the generated CRD
+        ///
+        /// This is the same schema that [`crd`](Self::crd) embeds, for assembling custom CRD layouts.
+        /// Returns `None` when schema generation is disabled with `#[kube(schema = "disabled")]` or `"manual"`.
+        fn schema() -> Option<super::apiexts::v1::JSONSchemaProps> {
+            Self::crd()
+                .spec
+                .versions
+                .into_iter()
+                .next()?
+                .schema?
+                .open_api_v3_schema
+        }
         /// Helper to return the name of this `CustomResourceDefinition` in kubernetes.
         ///
         /// This is not the name of an _instance_ of this custom resource but the `CustomResourceDefinition` object itself.
@@ -237,3 +250,38 @@ pub mod v1 {
 
 // re-export current latest (v1)
 pub use v1::{merge_crds, CustomResourceExt, MergeError};
+
+/// Recursively sorts the keys of every JSON object within `value`
+///
+/// `serde_json` keeps object keys in insertion order when its `preserve_order` feature is enabled anywhere
+/// in the dependency tree, so freeform values such as schema defaults can otherwise serialize
+/// in whatever order their source (e.g. a `HashMap`) produced them.
+/// Used by the `CustomResource` derive so that generated CRDs serialize with stable key ordering.
+#[doc(hidden)]
+pub fn sort_json_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                sort_json_keys(&mut value);
+                map.insert(key, value);
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(sort_json_keys),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::sort_json_keys;
+
+    #[test]
+    fn json_keys_sorted_recursively() {
+        let mut value: serde_json::Value =
+            serde_json::from_str(r#"{"b":1,"a":{"d":[{"z":1,"y":2}],"c":2}}"#).unwrap();
+        sort_json_keys(&mut value);
+        assert_eq!(value.to_string(), r#"{"a":{"c":2,"d":[{"y":2,"z":1}]},"b":1}"#);
+    }
+}
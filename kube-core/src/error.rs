@@ -1,7 +1,10 @@
+use crate::response::StatusDetails;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// An error response from the API.
+///
+/// This is the `metav1.Status` object returned by the apiserver for unsuccessful requests.
 #[derive(Error, Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 #[error("{message}: {reason}")]
 pub struct ErrorResponse {
@@ -15,4 +18,9 @@ pub struct ErrorResponse {
     pub reason: String,
     /// The error code
     pub code: u16,
+    /// Extended data associated with the reason, such as the fields that failed validation
+    ///
+    /// Boxed to keep errors that embed an `ErrorResponse` small.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Box<StatusDetails>>,
 }
@@ -26,7 +26,7 @@ pub mod gvk;
 pub use gvk::{GroupVersion, GroupVersionKind, GroupVersionResource};
 
 pub mod metadata;
-pub use metadata::{ListMeta, ObjectMeta, TypeMeta};
+pub use metadata::{ListMeta, ObjectMeta, PartialObjectMeta, TypeMeta};
 
 pub mod object;
 pub use object::{NotUsed, Object, ObjectList};
@@ -43,7 +43,7 @@ pub use resource::{
 };
 
 pub mod response;
-pub use response::Status;
+pub use response::{Created, DeleteOutcome, Ensured, Status};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
 #[cfg(feature = "schema")]
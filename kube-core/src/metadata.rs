@@ -1,7 +1,11 @@
 //! Metadata structs used in traits, lists, and dynamic objects.
+use std::borrow::Cow;
+
 pub use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ListMeta, ObjectMeta};
 use serde::{Deserialize, Serialize};
 
+use crate::{discovery::ApiResource, DynamicResourceScope, Resource};
+
 /// Type information that is flattened into every kubernetes object
 #[derive(Deserialize, Serialize, Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -12,3 +16,90 @@ pub struct TypeMeta {
     /// The name of the API
     pub kind: String,
 }
+
+/// A generic representation of any object with `ObjectMeta`.
+///
+/// It allows clients to get access to a particular `ObjectMeta`
+/// schema without knowing the details of the version.
+///
+/// This is what the apiserver returns for metadata-only requests such as
+/// [`Request::list_metadata`](crate::Request::list_metadata) and
+/// [`Request::watch_metadata`](crate::Request::watch_metadata).
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialObjectMeta {
+    /// The type fields, not always present
+    #[serde(flatten, default)]
+    pub types: Option<TypeMeta>,
+    /// Standard object's metadata
+    #[serde(default)]
+    pub metadata: ObjectMeta,
+}
+
+impl Resource for PartialObjectMeta {
+    type DynamicType = ApiResource;
+    type Scope = DynamicResourceScope;
+
+    fn kind(dt: &ApiResource) -> Cow<'_, str> {
+        dt.kind.as_str().into()
+    }
+
+    fn group(dt: &ApiResource) -> Cow<'_, str> {
+        dt.group.as_str().into()
+    }
+
+    fn version(dt: &ApiResource) -> Cow<'_, str> {
+        dt.version.as_str().into()
+    }
+
+    fn api_version(dt: &ApiResource) -> Cow<'_, str> {
+        dt.api_version.as_str().into()
+    }
+
+    fn plural(dt: &ApiResource) -> Cow<'_, str> {
+        dt.plural.as_str().into()
+    }
+
+    fn meta(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+
+    fn meta_mut(&mut self) -> &mut ObjectMeta {
+        &mut self.metadata
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PartialObjectMeta;
+    use crate::ResourceExt;
+
+    #[test]
+    fn partial_object_meta_deserializes_metadata_only() {
+        let pom: PartialObjectMeta = serde_json::from_value(serde_json::json!({
+            "apiVersion": "meta.k8s.io/v1",
+            "kind": "PartialObjectMetadata",
+            "metadata": {
+                "name": "big-config",
+                "namespace": "default",
+                "resourceVersion": "123",
+            },
+        }))
+        .unwrap();
+        assert_eq!(pom.types.unwrap().kind, "PartialObjectMetadata");
+        assert_eq!(pom.metadata.name.as_deref(), Some("big-config"));
+        assert_eq!(pom.metadata.resource_version.as_deref(), Some("123"));
+    }
+
+    #[test]
+    fn partial_object_meta_implements_resource() {
+        let pom = PartialObjectMeta {
+            types: None,
+            metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+                name: Some("foo".into()),
+                ..Default::default()
+            },
+        };
+        assert_eq!(pom.name_any(), "foo");
+    }
+}
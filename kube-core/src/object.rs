@@ -7,6 +7,10 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
+k8s_openapi::k8s_if_ge_1_19! {
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Condition;
+}
+
 /// A generic Kubernetes object list
 ///
 /// This is used instead of a full struct for `DeploymentList`, `PodList`, etc.
@@ -142,6 +146,56 @@ pub trait HasStatus {
     fn status_mut(&mut self) -> &mut Option<Self::Status>;
 }
 
+k8s_openapi::k8s_if_ge_1_19! {
+    /// A trait to access the `status.conditions` of a Kubernetes resource.
+    ///
+    /// This can be implemented by the kube-derive macro with `#[kube(conditions)]` for custom resources
+    /// whose status struct contains a `conditions: Vec<Condition>` field.
+    ///
+    /// The provided methods follow the semantics of apimachinery's `meta.SetStatusCondition` helpers,
+    /// where conditions are keyed by their `type`.
+    pub trait HasConditions: HasStatus {
+        /// Returns the conditions of the object, or an empty slice if the status is unset
+        fn conditions(&self) -> &[Condition];
+
+        /// Returns a mutable reference to the conditions of the object
+        ///
+        /// This initializes the `status` of the object if it is unset.
+        fn conditions_mut(&mut self) -> &mut Vec<Condition>;
+
+        /// Returns the condition of a given `type`, if present
+        fn get_condition(&self, type_: &str) -> Option<&Condition> {
+            self.conditions().iter().find(|c| c.type_ == type_)
+        }
+
+        /// Sets a condition, replacing any existing condition of the same `type`
+        ///
+        /// The `last_transition_time` of an existing condition is only changed if its `status` changes.
+        fn set_condition(&mut self, condition: Condition) {
+            let conditions = self.conditions_mut();
+            match conditions.iter_mut().find(|c| c.type_ == condition.type_) {
+                Some(existing) => {
+                    if existing.status != condition.status {
+                        existing.status = condition.status;
+                        existing.last_transition_time = condition.last_transition_time;
+                    }
+                    existing.reason = condition.reason;
+                    existing.message = condition.message;
+                    existing.observed_generation = condition.observed_generation;
+                }
+                None => conditions.push(condition),
+            }
+        }
+
+        /// Removes the condition of a given `type`, returning it if it was present
+        fn remove_condition(&mut self, type_: &str) -> Option<Condition> {
+            let conditions = self.conditions_mut();
+            let idx = conditions.iter().position(|c| c.type_ == type_)?;
+            Some(conditions.remove(idx))
+        }
+    }
+}
+
 // -------------------------------------------------------
 
 /// A standard Kubernetes object with `.spec` and `.status`.
@@ -18,7 +18,9 @@ pub struct ListParams {
     /// Timeout for the list/watch call.
     ///
     /// This limits the duration of the call, regardless of any activity or inactivity.
-    /// If unset for a watch call, we will use 290s.
+    /// If unset for a watch call, we will use [`ListParams::DEFAULT_WATCH_TIMEOUT`] (290s),
+    /// which stays just under common load balancer idle timeouts.
+    /// A value of `0` omits the timeout entirely, leaving the apiserver to pick its default.
     /// We limit this to 295s due to [inherent watch limitations](https://github.com/kubernetes/kubernetes/issues/6513).
     pub timeout: Option<u32>,
 
@@ -44,6 +46,27 @@ pub struct ListParams {
     ///
     /// After listing results with a limit, a continue token can be used to fetch another page of results.
     pub continue_token: Option<String>,
+
+    /// The resource version to list at.
+    ///
+    /// Only used by list calls; watches take their resource version separately.
+    /// Defaults to the most recent version, served by a quorum read, if `None`.
+    /// See the [Kubernetes API docs](https://kubernetes.io/docs/reference/using-api/api-concepts/#resource-versions)
+    /// for the semantics of each value.
+    pub resource_version: Option<String>,
+
+    /// How [`ListParams::resource_version`] is matched against the apiserver's state.
+    ///
+    /// Requires a `resource_version`, and is rejected with a watch. Requires Kubernetes 1.19.
+    pub resource_version_match: Option<ResourceVersionMatch>,
+
+    /// Stream the current state of the collection through a watch before any changes.
+    ///
+    /// The apiserver sends the existing objects as `ADDED` events, followed by a `BOOKMARK`
+    /// annotated with `k8s.io/initial-events-end`, which avoids a separate (and potentially large) list call.
+    /// Requires [`ListParams::bookmarks`], is rejected with a list, and requires Kubernetes 1.27
+    /// with the `WatchList` feature gate.
+    pub send_initial_events: bool,
 }
 
 impl Default for ListParams {
@@ -58,11 +81,17 @@ impl Default for ListParams {
             timeout: None,
             limit: None,
             continue_token: None,
+            resource_version: None,
+            resource_version_match: None,
+            send_initial_events: false,
         }
     }
 }
 
 impl ListParams {
+    /// The watch timeout in seconds used when [`ListParams::timeout`] is unset
+    pub const DEFAULT_WATCH_TIMEOUT: u32 = 290;
+
     pub(crate) fn validate(&self) -> Result<(), Error> {
         if let Some(to) = &self.timeout {
             // https://github.com/kubernetes/kubernetes/issues/6513
@@ -72,6 +101,28 @@ impl ListParams {
         }
         Ok(())
     }
+
+    // Combinations the apiserver rejects, see
+    // https://kubernetes.io/docs/reference/using-api/api-concepts/#resourceversion-in-requests
+    pub(crate) fn validate_resource_version(&self) -> Result<(), Error> {
+        if self.continue_token.is_some()
+            && (self.resource_version.is_some() || self.resource_version_match.is_some())
+        {
+            return Err(Error::Validation(
+                "ListParams::continue_token cannot be combined with a resource_version or resource_version_match"
+                    .into(),
+            ));
+        }
+        match (&self.resource_version_match, self.resource_version.as_deref()) {
+            (Some(_), None) => Err(Error::Validation(
+                "ListParams::resource_version_match requires a resource_version".into(),
+            )),
+            (Some(ResourceVersionMatch::Exact), Some("0")) => Err(Error::Validation(
+                "ListParams::resource_version_match cannot be Exact for resource_version 0".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Builder interface to ListParams
@@ -87,7 +138,7 @@ impl ListParams {
     /// Configure the timeout for list/watch calls
     ///
     /// This limits the duration of the call, regardless of any activity or inactivity.
-    /// Defaults to 290s
+    /// Defaults to 290s, and `0` defers to the apiserver's default.
     #[must_use]
     pub fn timeout(mut self, timeout_secs: u32) -> Self {
         self.timeout = Some(timeout_secs);
@@ -105,6 +156,40 @@ impl ListParams {
         self
     }
 
+    /// Restrict the list of returned objects to those where the field `key` equals `value`
+    ///
+    /// Can be chained, and is combined with any existing [`ListParams::fields`] selector.
+    /// Special characters in `value` are escaped, so it is matched literally.
+    ///
+    /// ```
+    /// use kube::api::ListParams;
+    /// let lp = ListParams::default()
+    ///     .fields_eq("status.phase", "Running")
+    ///     .fields_ne("spec.nodeName", "");
+    /// assert_eq!(lp.field_selector.unwrap(), "status.phase=Running,spec.nodeName!=");
+    /// ```
+    #[must_use]
+    pub fn fields_eq(mut self, key: &str, value: &str) -> Self {
+        push_requirement(
+            &mut self.field_selector,
+            format!("{key}={}", escape_field_value(value)),
+        );
+        self
+    }
+
+    /// Restrict the list of returned objects to those where the field `key` does not equal `value`
+    ///
+    /// Can be chained, and is combined with any existing [`ListParams::fields`] selector.
+    /// Special characters in `value` are escaped, so it is matched literally.
+    #[must_use]
+    pub fn fields_ne(mut self, key: &str, value: &str) -> Self {
+        push_requirement(
+            &mut self.field_selector,
+            format!("{key}!={}", escape_field_value(value)),
+        );
+        self
+    }
+
     /// Configure the selector to restrict the list of returned objects by their labels.
     ///
     /// Defaults to everything.
@@ -115,6 +200,68 @@ impl ListParams {
         self
     }
 
+    /// Restrict the list of returned objects to those with the label `key` set to `value`
+    ///
+    /// Can be chained, and is combined with any existing [`ListParams::labels`] selector.
+    /// Label values cannot contain selector syntax, so no escaping is needed.
+    ///
+    /// ```
+    /// use kube::api::ListParams;
+    /// let lp = ListParams::default()
+    ///     .labels_eq("app", "blog")
+    ///     .labels_in("tier", &["web", "api"])
+    ///     .labels_not_exists("canary");
+    /// assert_eq!(lp.label_selector.unwrap(), "app=blog,tier in (web,api),!canary");
+    /// ```
+    #[must_use]
+    pub fn labels_eq(mut self, key: &str, value: &str) -> Self {
+        push_requirement(&mut self.label_selector, format!("{key}={value}"));
+        self
+    }
+
+    /// Restrict the list of returned objects to those with the label `key` set to one of `values`
+    ///
+    /// An empty `values` matches nothing, as no value is in the empty set.
+    #[must_use]
+    pub fn labels_in(mut self, key: &str, values: &[&str]) -> Self {
+        let requirement = if values.is_empty() {
+            // The apiserver rejects `key in ()`, so require the label to both exist and not exist
+            format!("{key},!{key}")
+        } else {
+            format!("{key} in ({})", values.join(","))
+        };
+        push_requirement(&mut self.label_selector, requirement);
+        self
+    }
+
+    /// Restrict the list of returned objects to those without the label `key` set to any of `values`
+    ///
+    /// Objects that do not have the label at all also match, and an empty `values` adds no restriction.
+    #[must_use]
+    pub fn labels_not_in(mut self, key: &str, values: &[&str]) -> Self {
+        if !values.is_empty() {
+            push_requirement(
+                &mut self.label_selector,
+                format!("{key} notin ({})", values.join(",")),
+            );
+        }
+        self
+    }
+
+    /// Restrict the list of returned objects to those with the label `key`, regardless of its value
+    #[must_use]
+    pub fn labels_exists(mut self, key: &str) -> Self {
+        push_requirement(&mut self.label_selector, key.to_string());
+        self
+    }
+
+    /// Restrict the list of returned objects to those without the label `key`
+    #[must_use]
+    pub fn labels_not_exists(mut self, key: &str) -> Self {
+        push_requirement(&mut self.label_selector, format!("!{key}"));
+        self
+    }
+
     /// Disables watch bookmarks to simplify watch handling
     ///
     /// This is not recommended to use with production watchers as it can cause desyncs.
@@ -125,6 +272,23 @@ impl ListParams {
         self
     }
 
+    /// Configure whether the apiserver should send periodic progress notifications on watches
+    ///
+    /// This is an alias for the bookmarks setting: the apiserver implements watch progress notifications
+    /// as periodic `BOOKMARK` events, so `progress_notify(false)` is the same as
+    /// [`ListParams::disable_bookmarks`], and `progress_notify(true)` restores the default of sending them.
+    ///
+    /// Bookmarks are sent even when none of the watched objects change, which keeps the
+    /// resource version of a [`watcher`] fresh, and avoids `410 Gone` errors on re-watches of
+    /// low-traffic resources while the global resource version keeps moving.
+    ///
+    /// [`watcher`]: https://docs.rs/kube_runtime/*/kube_runtime/watcher/fn.watcher.html
+    #[must_use]
+    pub fn progress_notify(mut self, enabled: bool) -> Self {
+        self.bookmarks = enabled;
+        self
+    }
+
     /// Sets a result limit.
     #[must_use]
     pub fn limit(mut self, limit: u32) -> Self {
@@ -133,11 +297,92 @@ impl ListParams {
     }
 
     /// Sets a continue token.
+    ///
+    /// The token already pins the resource version of the list, so it cannot be combined
+    /// with [`ListParams::at`] or [`ListParams::matching`].
     #[must_use]
     pub fn continue_token(mut self, token: &str) -> Self {
         self.continue_token = Some(token.to_string());
         self
     }
+
+    /// Sets the resource version to list at.
+    ///
+    /// With no [`ListParams::matching`] this is treated as `NotOlderThan`,
+    /// except for `"0"` which means any version, e.g. from the apiserver's cache.
+    #[must_use]
+    pub fn at(mut self, resource_version: &str) -> Self {
+        self.resource_version = Some(resource_version.to_string());
+        self
+    }
+
+    /// Sets how the resource version given to [`ListParams::at`] is matched.
+    ///
+    /// ```
+    /// use kube::api::{ListParams, ResourceVersionMatch};
+    /// let lp = ListParams::default().at("1337").matching(ResourceVersionMatch::Exact);
+    /// ```
+    #[must_use]
+    pub fn matching(mut self, version_match: ResourceVersionMatch) -> Self {
+        self.resource_version_match = Some(version_match);
+        self
+    }
+
+    /// Stream the initial state of the collection through watches, rather than listing it.
+    ///
+    /// See [`ListParams::send_initial_events`] for the requirements.
+    #[must_use]
+    pub fn send_initial_events(mut self, enabled: bool) -> Self {
+        self.send_initial_events = enabled;
+        self
+    }
+}
+
+// Appends a requirement to a comma separated selector
+fn push_requirement(selector: &mut Option<String>, requirement: String) {
+    match selector {
+        Some(existing) if !existing.is_empty() => {
+            existing.push(',');
+            existing.push_str(&requirement);
+        }
+        _ => *selector = Some(requirement),
+    }
+}
+
+// Escapes the characters with a meaning in field selectors, like apimachinery's `fields.EscapeValue`
+fn escape_field_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ',' | '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// How a resource version given to a list call is matched
+///
+/// See the [Kubernetes API docs](https://kubernetes.io/docs/reference/using-api/api-concepts/#resourceversion-in-requests)
+/// for the semantics of each variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceVersionMatch {
+    /// Return data at exactly the given resource version.
+    ///
+    /// Fails with `410 Gone` if the version has been compacted away.
+    Exact,
+    /// Return data at least as new as the given resource version.
+    NotOlderThan,
+}
+
+impl ResourceVersionMatch {
+    /// Returns the string format of the match
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Exact => "Exact",
+            Self::NotOlderThan => "NotOlderThan",
+        }
+    }
 }
 
 /// The validation directive to use for `fieldValidation` when using server-side apply.
@@ -242,7 +487,11 @@ pub enum Patch<T: Serialize> {
     /// Requires kubernetes >= 1.16
     Apply(T),
 
-    /// [JSON patch](https://kubernetes.io/docs/tasks/run-application/update-api-object-kubectl-patch/#use-a-json-merge-patch-to-update-a-deployment)
+    /// [JSON patch](https://datatracker.ietf.org/doc/html/rfc6902)
+    ///
+    /// A list of `add`, `remove`, `replace`, `move`, `copy` and `test` operations on paths in the object,
+    /// which allows precise changes that a merge patch cannot express, such as removing a list element by index.
+    /// The operations are applied in order, and the whole patch is rejected if any of them fails.
     ///
     /// Using this variant will require you to explicitly provide a type for `T` at the moment.
     ///
@@ -250,7 +499,17 @@ pub enum Patch<T: Serialize> {
     ///
     /// ```
     /// use kube::api::Patch;
-    /// let json_patch = json_patch::Patch(vec![]);
+    /// use json_patch::{PatchOperation, RemoveOperation, TestOperation};
+    /// // Remove the first finalizer, as long as it is still the one we expect
+    /// let json_patch = json_patch::Patch(vec![
+    ///     PatchOperation::Test(TestOperation {
+    ///         path: "/metadata/finalizers/0".into(),
+    ///         value: "example.com/cleanup".into(),
+    ///     }),
+    ///     PatchOperation::Remove(RemoveOperation {
+    ///         path: "/metadata/finalizers/0".into(),
+    ///     }),
+    /// ]);
     /// let patch = Patch::Json::<()>(json_patch);
     /// ```
     #[cfg(feature = "jsonpatch")]
@@ -501,7 +760,42 @@ where
 }
 #[cfg(test)]
 mod test {
-    use super::{DeleteParams, PatchParams, ValidationDirective};
+    use super::{DeleteParams, ListParams, PatchParams, ValidationDirective};
+
+    #[test]
+    fn label_selector_set_based_builders() {
+        let lp = ListParams::default()
+            .labels("app=blog")
+            .labels_in("env", &["prod", "staging"])
+            .labels_not_in("tier", &["cache"])
+            .labels_exists("team")
+            .labels_not_exists("canary");
+        assert_eq!(
+            lp.label_selector.as_deref(),
+            Some("app=blog,env in (prod,staging),tier notin (cache),team,!canary")
+        );
+
+        let lp = ListParams::default().labels_exists("kubernetes.io/metadata.name");
+        assert_eq!(lp.label_selector.as_deref(), Some("kubernetes.io/metadata.name"));
+
+        let lp = ListParams::default()
+            .labels_not_in("tier", &[])
+            .labels_in("env", &[]);
+        assert_eq!(lp.label_selector.as_deref(), Some("env,!env"));
+    }
+
+    #[test]
+    fn field_selector_builder() {
+        let lp = ListParams::default()
+            .fields("metadata.namespace=default")
+            .fields_eq("metadata.name", "a,b=c\\d")
+            .fields_ne("status.phase", "Running");
+        assert_eq!(
+            lp.field_selector.as_deref(),
+            Some(r"metadata.namespace=default,metadata.name=a\,b\=c\\d,status.phase!=Running")
+        );
+    }
+
     #[test]
     fn delete_param_serialize() {
         let mut dp = DeleteParams::default();
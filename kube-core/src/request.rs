@@ -4,6 +4,16 @@ use thiserror::Error;
 use super::params::{DeleteParams, ListParams, Patch, PatchParams, PostParams};
 
 pub(crate) const JSON_MIME: &str = "application/json";
+/// Extended Accept Header
+///
+/// Requests a meta.k8s.io/v1 PartialObjectMetadata resource (efficiently
+/// retrieves object metadata)
+///
+/// API Servers running Kubernetes v1.14 and below will retrieve the object and then
+/// convert the metadata.
+pub(crate) const JSON_METADATA_MIME: &str = "application/json;as=PartialObjectMetadata;g=meta.k8s.io;v=v1";
+pub(crate) const JSON_METADATA_LIST_MIME: &str =
+    "application/json;as=PartialObjectMetadataList;g=meta.k8s.io;v=v1";
 
 /// Possible errors when building a request.
 #[derive(Debug, Error)]
@@ -46,6 +56,12 @@ impl Request {
     pub fn list(&self, lp: &ListParams) -> Result<http::Request<Vec<u8>>, Error> {
         let target = format!("{}?", self.url_path);
         let mut qp = form_urlencoded::Serializer::new(target);
+        lp.validate_resource_version()?;
+        if lp.send_initial_events {
+            return Err(Error::Validation(
+                "ListParams::send_initial_events can only be used with a watch.".into(),
+            ));
+        }
 
         if let Some(fields) = &lp.field_selector {
             qp.append_pair("fieldSelector", fields);
@@ -59,6 +75,12 @@ impl Request {
         if let Some(continue_token) = &lp.continue_token {
             qp.append_pair("continue", continue_token);
         }
+        if let Some(rv) = &lp.resource_version {
+            qp.append_pair("resourceVersion", rv);
+        }
+        if let Some(rv_match) = &lp.resource_version_match {
+            qp.append_pair("resourceVersionMatch", rv_match.as_str());
+        }
 
         let urlstr = qp.finish();
         let req = http::Request::get(urlstr);
@@ -80,12 +102,27 @@ impl Request {
                 "ListParams::continue_token cannot be used with a watch.".into(),
             ));
         }
+        if lp.resource_version.is_some() || lp.resource_version_match.is_some() {
+            return Err(Error::Validation(
+                "ListParams::resource_version cannot be used with a watch, pass the version to watch from instead.".into(),
+            ));
+        }
+        if lp.send_initial_events && !lp.bookmarks {
+            return Err(Error::Validation(
+                "ListParams::send_initial_events requires bookmarks.".into(),
+            ));
+        }
 
         qp.append_pair("watch", "true");
         qp.append_pair("resourceVersion", ver);
 
         // https://github.com/kubernetes/kubernetes/issues/6513
-        qp.append_pair("timeoutSeconds", &lp.timeout.unwrap_or(290).to_string());
+        match lp.timeout.unwrap_or(ListParams::DEFAULT_WATCH_TIMEOUT) {
+            0 => {}
+            timeout => {
+                qp.append_pair("timeoutSeconds", &timeout.to_string());
+            }
+        }
         if let Some(fields) = &lp.field_selector {
             qp.append_pair("fieldSelector", fields);
         }
@@ -95,6 +132,10 @@ impl Request {
         if lp.bookmarks {
             qp.append_pair("allowWatchBookmarks", "true");
         }
+        if lp.send_initial_events {
+            qp.append_pair("sendInitialEvents", "true");
+            qp.append_pair("resourceVersionMatch", "NotOlderThan");
+        }
 
         let urlstr = qp.finish();
         let req = http::Request::get(urlstr);
@@ -110,6 +151,18 @@ impl Request {
         req.body(vec![]).map_err(Error::BuildRequest)
     }
 
+    /// Get a single instance from the apiserver watch cache
+    ///
+    /// Sets `resourceVersion=0`, allowing the apiserver to answer without a quorum read.
+    pub fn get_cached(&self, name: &str) -> Result<http::Request<Vec<u8>>, Error> {
+        let target = format!("{}/{}?", self.url_path, name);
+        let mut qp = form_urlencoded::Serializer::new(target);
+        qp.append_pair("resourceVersion", "0");
+        let urlstr = qp.finish();
+        let req = http::Request::get(urlstr);
+        req.body(vec![]).map_err(Error::BuildRequest)
+    }
+
     /// Create an instance of a resource
     pub fn create(&self, pp: &PostParams, data: Vec<u8>) -> Result<http::Request<Vec<u8>>, Error> {
         pp.validate()?;
@@ -195,6 +248,43 @@ impl Request {
     }
 }
 
+/// Metadata-only request implementations
+///
+/// Requests set an extended Accept header compromised of JSON media type params, with
+/// the apiserver returning [`PartialObjectMeta`](crate::PartialObjectMeta) objects
+/// instead of the full objects.
+impl Request {
+    /// Get a single metadata instance for a named resource
+    pub fn get_metadata(&self, name: &str) -> Result<http::Request<Vec<u8>>, Error> {
+        let mut req = self.get(name)?;
+        req.headers_mut().insert(
+            http::header::ACCEPT,
+            http::HeaderValue::from_static(JSON_METADATA_MIME),
+        );
+        Ok(req)
+    }
+
+    /// List a collection of metadata of a resource
+    pub fn list_metadata(&self, lp: &ListParams) -> Result<http::Request<Vec<u8>>, Error> {
+        let mut req = self.list(lp)?;
+        req.headers_mut().insert(
+            http::header::ACCEPT,
+            http::HeaderValue::from_static(JSON_METADATA_LIST_MIME),
+        );
+        Ok(req)
+    }
+
+    /// Watch metadata of a resource at a given version
+    pub fn watch_metadata(&self, lp: &ListParams, ver: &str) -> Result<http::Request<Vec<u8>>, Error> {
+        let mut req = self.watch(lp, ver)?;
+        req.headers_mut().insert(
+            http::header::ACCEPT,
+            http::HeaderValue::from_static(JSON_METADATA_MIME),
+        );
+        Ok(req)
+    }
+}
+
 /// Subresources
 impl Request {
     /// Get an instance of the subresource
@@ -210,6 +300,25 @@ impl Request {
         req.body(vec![]).map_err(Error::BuildRequest)
     }
 
+    /// Create an instance of the subresource
+    pub fn create_subresource(
+        &self,
+        subresource_name: &str,
+        name: &str,
+        pp: &PostParams,
+        data: Vec<u8>,
+    ) -> Result<http::Request<Vec<u8>>, Error> {
+        pp.validate()?;
+        let target = format!("{}/{}/{}?", self.url_path, name, subresource_name);
+        let mut qp = form_urlencoded::Serializer::new(target);
+        if pp.dry_run {
+            qp.append_pair("dryRun", "All");
+        }
+        let urlstr = qp.finish();
+        let req = http::Request::post(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
+        req.body(data).map_err(Error::BuildRequest)
+    }
+
     /// Patch an instance of the subresource
     pub fn patch_subresource<P: serde::Serialize>(
         &self,
@@ -378,6 +487,28 @@ mod test {
         assert_eq!(req.uri(), "/apis/apps/v1/namespaces/ns/deployments");
     }
     #[test]
+    fn list_resource_version_match() {
+        use crate::params::ResourceVersionMatch;
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = ListParams::default()
+            .at("1337")
+            .matching(ResourceVersionMatch::NotOlderThan);
+        let req = Request::new(&url).list(&lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&resourceVersion=1337&resourceVersionMatch=NotOlderThan"
+        );
+        let unmatched = ListParams::default().matching(ResourceVersionMatch::NotOlderThan);
+        assert!(Request::new(&url).list(&unmatched).is_err());
+        let exact_zero = ListParams::default()
+            .at("0")
+            .matching(ResourceVersionMatch::Exact);
+        assert!(Request::new(&url).list(&exact_zero).is_err());
+        assert!(Request::new(&url).watch(&lp, "0").is_err());
+        let continued = ListParams::default().at("1337").continue_token("page2");
+        assert!(Request::new(&url).list(&continued).is_err());
+    }
+    #[test]
     fn watch_path() {
         let url = corev1::Pod::url_path(&(), Some("ns"));
         let gp = ListParams::default();
@@ -388,6 +519,56 @@ mod test {
         );
     }
     #[test]
+    fn watch_send_initial_events() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = ListParams::default().send_initial_events(true);
+        let req = Request::new(&url).watch(&lp, "").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=&timeoutSeconds=290&allowWatchBookmarks=true&sendInitialEvents=true&resourceVersionMatch=NotOlderThan"
+        );
+        assert!(Request::new(&url).list(&lp).is_err());
+        let no_bookmarks = lp.disable_bookmarks();
+        assert!(Request::new(&url).watch(&no_bookmarks, "").is_err());
+    }
+    #[test]
+    fn watch_timeout() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = ListParams::default().disable_bookmarks();
+        let req = Request::new(&url).watch(&lp, "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=290"
+        );
+        let req = Request::new(&url).watch(&lp.clone().timeout(30), "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=30"
+        );
+        let req = Request::new(&url).watch(&lp.clone().timeout(0), "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0"
+        );
+        assert!(Request::new(url).watch(&lp.timeout(295), "0").is_err());
+    }
+    #[test]
+    fn watch_progress_notify() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = ListParams::default().progress_notify(false);
+        let req = Request::new(&url).watch(&lp, "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=290"
+        );
+        let lp = lp.progress_notify(true);
+        let req = Request::new(url).watch(&lp, "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=290&allowWatchBookmarks=true"
+        );
+    }
+    #[test]
     fn replace_path() {
         let url = appsv1::DaemonSet::url_path(&(), None);
         let pp = PostParams {
@@ -455,6 +636,27 @@ mod test {
         );
         assert_eq!(req.method(), "PATCH");
     }
+    #[cfg(feature = "jsonpatch")]
+    #[test]
+    fn json_patch_path() {
+        use json_patch::{PatchOperation, RemoveOperation};
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let pp = PatchParams::default();
+        let patch = Patch::Json::<()>(json_patch::Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: "/metadata/finalizers/0".into(),
+        })]));
+        let req = Request::new(url).patch("mypod", &pp, &patch).unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod?");
+        assert_eq!(req.method(), "PATCH");
+        assert_eq!(
+            req.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/json-patch+json"
+        );
+        assert_eq!(
+            req.body().as_slice(),
+            br#"[{"op":"remove","path":"/metadata/finalizers/0"}]"#
+        );
+    }
     #[test]
     fn replace_status_path() {
         let url = corev1::Node::url_path(&(), None);
@@ -470,6 +672,21 @@ mod test {
         );
     }
 
+    #[test]
+    fn create_binding_path() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let pp = PostParams::default();
+        let req = Request::new(url)
+            .create_subresource("binding", "mypod", &pp, vec![])
+            .unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod/binding?");
+        assert_eq!(req.method(), "POST");
+        assert_eq!(
+            req.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            super::JSON_MIME
+        );
+    }
+
     #[test]
     fn create_ingress() {
         // NB: Ingress exists in extensions AND networking
@@ -536,6 +753,57 @@ mod test {
     //    let _r = Request::<corev1::Node>::new(&(), Some("ns"));
     //}
 
+    #[test]
+    fn list_metadata_path() {
+        let url = corev1::ConfigMap::url_path(&(), Some("ns"));
+        let lp = ListParams::default().labels("app=myapp");
+        let req = Request::new(url).list_metadata(&lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/configmaps?&labelSelector=app%3Dmyapp"
+        );
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::JSON_METADATA_LIST_MIME
+        );
+    }
+
+    #[test]
+    fn watch_metadata_path() {
+        let url = corev1::Secret::url_path(&(), Some("ns"));
+        let lp = ListParams::default();
+        let req = Request::new(url).watch_metadata(&lp, "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/secrets?&watch=true&resourceVersion=0&timeoutSeconds=290&allowWatchBookmarks=true"
+        );
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::JSON_METADATA_MIME
+        );
+    }
+
+    #[test]
+    fn get_cached_path() {
+        let url = corev1::Secret::url_path(&(), Some("ns"));
+        let req = Request::new(url).get_cached("mysecret").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/secrets/mysecret?&resourceVersion=0"
+        );
+    }
+
+    #[test]
+    fn get_metadata_path() {
+        let url = corev1::Secret::url_path(&(), Some("ns"));
+        let req = Request::new(url).get_metadata("mysecret").unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/secrets/mysecret");
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::JSON_METADATA_MIME
+        );
+    }
+
     #[test]
     fn watches_cannot_have_limits() {
         let lp = ListParams::default().limit(5);
@@ -103,13 +103,25 @@ pub trait Resource {
     /// Note: this returns an `Option`, but for objects populated from the apiserver,
     /// this Option can be safely unwrapped.
     fn controller_owner_ref(&self, dt: &Self::DynamicType) -> Option<OwnerReference> {
+        Some(OwnerReference {
+            controller: Some(true),
+            ..self.owner_ref(dt)?
+        })
+    }
+
+    /// Generates an owner reference pointing to this resource
+    ///
+    /// Unlike [`Resource::controller_owner_ref`], the reference does not mark this resource as the managing controller,
+    /// so it only ties the lifetime of the owned object to this resource for garbage collection.
+    ///
+    /// Returns `None` if the name or uid is missing, since the apiserver rejects owner references without them.
+    fn owner_ref(&self, dt: &Self::DynamicType) -> Option<OwnerReference> {
         let meta = self.meta();
         Some(OwnerReference {
             api_version: Self::api_version(dt).to_string(),
             kind: Self::kind(dt).to_string(),
             name: meta.name.clone()?,
             uid: meta.uid.clone()?,
-            controller: Some(true),
             ..OwnerReference::default()
         })
     }
@@ -208,6 +220,10 @@ pub trait ResourceExt: Resource {
     fn owner_references(&self) -> &[OwnerReference];
     /// Provides mutable access to the owner references
     fn owner_references_mut(&mut self) -> &mut Vec<OwnerReference>;
+    /// Whether the resource has a controller owner reference pointing to the object with `parent_uid`
+    ///
+    /// Non-controller owner references are ignored, as they do not grant ownership for reconciliation.
+    fn is_owned_by(&self, parent_uid: &str) -> bool;
     /// Returns resource finalizers
     fn finalizers(&self) -> &[String];
     /// Provides mutable access to the finalizers
@@ -280,6 +296,12 @@ impl<K: Resource> ResourceExt for K {
         self.meta_mut().owner_references.get_or_insert_with(Vec::new)
     }
 
+    fn is_owned_by(&self, parent_uid: &str) -> bool {
+        self.owner_references()
+            .iter()
+            .any(|owner| owner.controller == Some(true) && owner.uid == parent_uid)
+    }
+
     fn finalizers(&self) -> &[String] {
         self.meta().finalizers.as_deref().unwrap_or_default()
     }
@@ -296,3 +318,46 @@ impl<K: Resource> ResourceExt for K {
         self.meta_mut().managed_fields.get_or_insert_with(Vec::new)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Resource, ResourceExt};
+    use k8s_openapi::api::{apps::v1::ReplicaSet, core::v1::Pod};
+
+    #[test]
+    fn owner_refs_require_name_and_uid() {
+        let mut rs = ReplicaSet::default();
+        rs.metadata.name = Some("blog".into());
+        assert!(rs.owner_ref(&()).is_none());
+        assert!(rs.controller_owner_ref(&()).is_none());
+
+        rs.metadata.uid = Some("1234".into());
+        let owner = rs.owner_ref(&()).unwrap();
+        assert_eq!(owner.api_version, "apps/v1");
+        assert_eq!(owner.kind, "ReplicaSet");
+        assert_eq!(owner.name, "blog");
+        assert_eq!(owner.uid, "1234");
+        assert_eq!(owner.controller, None);
+        assert_eq!(rs.controller_owner_ref(&()).unwrap().controller, Some(true));
+    }
+
+    #[test]
+    fn is_owned_by_checks_controller_references() {
+        let mut rs = ReplicaSet::default();
+        rs.metadata.name = Some("blog".into());
+        rs.metadata.uid = Some("1234".into());
+
+        let mut pod = Pod::default();
+        assert!(!pod.is_owned_by("1234"));
+
+        let mut owner = rs.controller_owner_ref(&()).unwrap();
+        owner.controller = Some(false);
+        pod.owner_references_mut().push(owner.clone());
+        assert!(!pod.is_owned_by("1234"));
+
+        owner.controller = Some(true);
+        pod.owner_references_mut().push(owner);
+        assert!(pod.is_owned_by("1234"));
+        assert!(!pod.is_owned_by("5678"));
+    }
+}
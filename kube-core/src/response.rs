@@ -1,5 +1,7 @@
 //! Generic api response types
-use serde::Deserialize;
+use crate::Resource;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 
 /// A Kubernetes status object
 ///
@@ -36,7 +38,7 @@ pub struct Status {
 }
 
 /// Status details object on the [`Status`] object
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusDetails {
     /// The name attribute of the resource associated with the status StatusReason (when there is a single name which can be described)
@@ -69,12 +71,16 @@ pub struct StatusDetails {
     ///
     /// Some errors may indicate the client must take an alternate action -
     /// for those errors this field may indicate how long to wait before taking the alternate action.
-    #[serde(default, skip_serializing_if = "num::Zero::is_zero")]
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub retry_after_seconds: u32,
 }
 
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 /// Status cause object on the [`StatusDetails`] object
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 pub struct StatusCause {
     /// A machine-readable description of the cause of the error. If this value is empty there is no information available.
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -92,9 +98,115 @@ pub struct StatusCause {
     pub field: String,
 }
 
+/// An object freshly created by the apiserver
+///
+/// Wraps the `K` returned from a create call, and exposes the identity that the apiserver
+/// assigned to it (such as a name generated from `metadata.generateName`) without having to
+/// dig into `metadata`.
+///
+/// Derefs to the underlying `K`, use [`Created::into_inner`] to take ownership of it.
+#[derive(Clone, Debug)]
+pub struct Created<K> {
+    object: K,
+}
+
+impl<K: Resource> Created<K> {
+    /// Wrap an object returned from the apiserver after a create call
+    pub fn new(object: K) -> Self {
+        Self { object }
+    }
+
+    /// The name of the created object
+    ///
+    /// This is the generated name when the object was created with `metadata.generateName`.
+    pub fn name(&self) -> &str {
+        self.object.meta().name.as_deref().unwrap_or_default()
+    }
+
+    /// The namespace of the created object, if it is namespaced
+    pub fn namespace(&self) -> Option<&str> {
+        self.object.meta().namespace.as_deref()
+    }
+
+    /// The unique id assigned to the created object
+    pub fn uid(&self) -> &str {
+        self.object.meta().uid.as_deref().unwrap_or_default()
+    }
+
+    /// The resource version of the created object
+    ///
+    /// This is empty for objects created with `dry_run`, as they are never persisted.
+    pub fn resource_version(&self) -> &str {
+        self.object.meta().resource_version.as_deref().unwrap_or_default()
+    }
+
+    /// Consume the wrapper and return the created object
+    pub fn into_inner(self) -> K {
+        self.object
+    }
+}
+
+impl<K> Deref for Created<K> {
+    type Target = K;
+
+    fn deref(&self) -> &K {
+        &self.object
+    }
+}
+
+/// The outcome of [`Api::ensure`](https://docs.rs/kube/*/kube/struct.Api.html#method.ensure)
+///
+/// Both variants carry the object as it exists on the apiserver after the call.
+#[derive(Clone, Debug)]
+pub enum Ensured<K> {
+    /// The live object already matched the desired fields, so no patch was sent
+    Unchanged(K),
+    /// The live object differed from the desired fields and was patched
+    Patched(K),
+}
+
+impl<K> Ensured<K> {
+    /// Whether a patch was sent to the apiserver
+    pub fn is_patched(&self) -> bool {
+        matches!(self, Self::Patched(_))
+    }
+
+    /// Consume the outcome and return the object
+    pub fn into_inner(self) -> K {
+        match self {
+            Self::Unchanged(obj) | Self::Patched(obj) => obj,
+        }
+    }
+}
+
+/// The outcome of [`Api::delete_with_outcome`](https://docs.rs/kube/*/kube/struct.Api.html#method.delete_with_outcome)
+#[derive(Clone, Debug)]
+pub enum DeleteOutcome<K> {
+    /// The object has a `deletionTimestamp` but still exists, pending its finalizers
+    Terminating(K),
+    /// The object was removed, with its final state if the apiserver returned it
+    Removed(Option<K>),
+}
+
+impl<K: Resource> DeleteOutcome<K> {
+    /// Classify an object returned from a delete call by its `deletionTimestamp`
+    pub fn from_object(object: K) -> Self {
+        if object.meta().deletion_timestamp.is_some() {
+            Self::Terminating(object)
+        } else {
+            Self::Removed(Some(object))
+        }
+    }
+
+    /// Whether the object was removed immediately
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Self::Removed(_))
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Status;
+    use super::{Created, DeleteOutcome, Status};
 
     // ensure our status schema is sensible
     #[test]
@@ -107,4 +219,49 @@ mod test {
         let s2: Status = serde_json::from_str::<Status>(statusnoname).unwrap();
         assert_eq!(s2.details.unwrap().name, ""); // optional probably better..
     }
+
+    #[test]
+    fn created_exposes_assigned_identity() {
+        use k8s_openapi::api::core::v1::Pod;
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": {
+                "name": "worker-x7f2k",
+                "generateName": "worker-",
+                "namespace": "default",
+                "uid": "8f3b9a1c-0000-0000-0000-000000000000",
+                "resourceVersion": "4242",
+            },
+        }))
+        .unwrap();
+        let created = Created::new(pod);
+        assert_eq!(created.name(), "worker-x7f2k");
+        assert_eq!(created.namespace(), Some("default"));
+        assert_eq!(created.uid(), "8f3b9a1c-0000-0000-0000-000000000000");
+        assert_eq!(created.resource_version(), "4242");
+        assert_eq!(created.metadata.generate_name.as_deref(), Some("worker-"));
+        assert_eq!(
+            created.into_inner().metadata.name.as_deref(),
+            Some("worker-x7f2k")
+        );
+    }
+
+    #[test]
+    fn delete_outcome_classifies_by_deletion_timestamp() {
+        use k8s_openapi::{
+            api::core::v1::Pod,
+            apimachinery::pkg::apis::meta::v1::Time,
+            chrono::{TimeZone, Utc},
+        };
+        let mut pod = Pod::default();
+        assert!(DeleteOutcome::from_object(pod.clone()).is_removed());
+
+        pod.metadata.deletion_timestamp = Some(Time(Utc.timestamp_opt(0, 0).unwrap()));
+        pod.metadata.finalizers = Some(vec!["kube-rs.dev/cleanup".into()]);
+        assert!(matches!(
+            DeleteOutcome::from_object(pod),
+            DeleteOutcome::Terminating(_)
+        ));
+    }
 }
@@ -4,7 +4,7 @@
 
 use crate::{error::ErrorResponse, metadata::TypeMeta};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug};
 /// A raw event returned from a watch query
 ///
 /// Note that a watch query returns many of these as newline separated JSON.
@@ -39,6 +39,31 @@ impl<K> Debug for WatchEvent<K> {
     }
 }
 
+impl<K> WatchEvent<K> {
+    /// The object carried by an `Added`, `Modified` or `Deleted` event
+    ///
+    /// Returns `None` for `Bookmark` and `Error` events, which do not carry a `K`.
+    pub fn into_inner(self) -> Option<K> {
+        match self {
+            WatchEvent::Added(obj) | WatchEvent::Modified(obj) | WatchEvent::Deleted(obj) => Some(obj),
+            WatchEvent::Bookmark(_) | WatchEvent::Error(_) => None,
+        }
+    }
+
+    /// Map the object carried by an `Added`, `Modified` or `Deleted` event
+    ///
+    /// `Bookmark` and `Error` events are passed through unchanged.
+    pub fn map<U>(self, f: impl FnOnce(K) -> U) -> WatchEvent<U> {
+        match self {
+            WatchEvent::Added(obj) => WatchEvent::Added(f(obj)),
+            WatchEvent::Modified(obj) => WatchEvent::Modified(f(obj)),
+            WatchEvent::Deleted(obj) => WatchEvent::Deleted(f(obj)),
+            WatchEvent::Bookmark(bm) => WatchEvent::Bookmark(bm),
+            WatchEvent::Error(err) => WatchEvent::Error(err),
+        }
+    }
+}
+
 /// Slimed down K for [`WatchEvent::Bookmark`] due to [#285](https://github.com/kube-rs/kube-rs/issues/285).
 ///
 /// Can only be relied upon to have metadata with resource version.
@@ -54,9 +79,90 @@ pub struct Bookmark {
 }
 
 /// Slimed down Metadata for WatchEvent::Bookmark
+///
+/// This only carries the fields the apiserver sets on bookmarks, and may grow more of them,
+/// so it cannot be constructed outside of deserialization.
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct BookmarkMeta {
     /// The only field we need from a Bookmark event.
     pub resource_version: String,
+
+    /// Annotations set by the apiserver, such as `k8s.io/initial-events-end`
+    #[serde(default)]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bookmark, BookmarkMeta, WatchEvent};
+    use crate::{error::ErrorResponse, metadata::TypeMeta};
+    use k8s_openapi::api::core::v1::Pod;
+
+    fn bookmark() -> Bookmark {
+        Bookmark {
+            types: TypeMeta {
+                api_version: "v1".into(),
+                kind: "Pod".into(),
+            },
+            metadata: BookmarkMeta {
+                resource_version: "1".into(),
+                annotations: Default::default(),
+            },
+        }
+    }
+
+    fn error() -> ErrorResponse {
+        ErrorResponse {
+            status: "Failure".into(),
+            message: "too old resource version".into(),
+            reason: "Expired".into(),
+            code: 410,
+            details: None,
+        }
+    }
+
+    #[test]
+    fn into_inner_returns_objects_only() {
+        assert_eq!(WatchEvent::Added(1).into_inner(), Some(1));
+        assert_eq!(WatchEvent::Modified(2).into_inner(), Some(2));
+        assert_eq!(WatchEvent::Deleted(3).into_inner(), Some(3));
+        assert_eq!(WatchEvent::<i32>::Bookmark(bookmark()).into_inner(), None);
+        assert_eq!(WatchEvent::<i32>::Error(error()).into_inner(), None);
+    }
+
+    #[test]
+    fn map_keeps_the_event_type() {
+        let double = |x: i32| x * 2;
+        assert!(matches!(WatchEvent::Added(1).map(double), WatchEvent::Added(2)));
+        assert!(matches!(
+            WatchEvent::Modified(2).map(double),
+            WatchEvent::Modified(4)
+        ));
+        assert!(matches!(
+            WatchEvent::Deleted(3).map(double),
+            WatchEvent::Deleted(6)
+        ));
+        assert!(matches!(
+            WatchEvent::Bookmark(bookmark()).map(double),
+            WatchEvent::Bookmark(bm) if bm.metadata.resource_version == "1"
+        ));
+        assert!(matches!(
+            WatchEvent::Error(error()).map(double),
+            WatchEvent::Error(err) if err.code == 410
+        ));
+    }
+
+    #[test]
+    fn bookmark_events_only_need_a_resource_version() {
+        let json = r#"{"type":"BOOKMARK","object":{"kind":"Pod","apiVersion":"v1","metadata":{"resourceVersion":"3845","creationTimestamp":null},"spec":{"containers":null},"status":{}}}"#;
+        match serde_json::from_str::<WatchEvent<Pod>>(json).unwrap() {
+            WatchEvent::Bookmark(bm) => {
+                assert_eq!(bm.metadata.resource_version, "3845");
+                assert_eq!(bm.types.kind, "Pod");
+            }
+            event => panic!("expected a bookmark, got {event:?}"),
+        }
+    }
 }
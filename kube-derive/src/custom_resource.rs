@@ -1,13 +1,18 @@
-use darling::{FromDeriveInput, FromMeta};
+use darling::{util::Flag, FromDeriveInput, FromField, FromMeta};
 use proc_macro2::{Ident, Span, TokenStream};
-use syn::{parse_quote, Data, DeriveInput, Path, Visibility};
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Fields, Generics, Lit, Meta, NestedMeta, Path, Type,
+    Visibility,
+};
 
 /// Values we can parse from #[kube(attrs)]
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(kube))]
 struct KubeAttrs {
     group: String,
-    version: String,
+    /// one or more versions sharing the schema (required, but repeatable so darling cannot enforce it)
+    #[darling(multiple, rename = "version")]
+    versions: Vec<Version>,
     kind: String,
     #[darling(rename = "struct")]
     kind_struct: Option<String>,
@@ -17,21 +22,230 @@ struct KubeAttrs {
     singular: Option<String>,
     #[darling(default)]
     namespaced: bool,
+    /// whether the version is served (defaults to true)
+    served: Option<bool>,
+    /// whether the version is the storage version (defaults to true)
+    storage: Option<bool>,
+    deprecated: Flag,
+    deprecation_warning: Option<String>,
     #[darling(multiple, rename = "derive")]
     derives: Vec<String>,
     schema: Option<SchemaMode>,
     status: Option<String>,
+    conditions: Flag,
+    preserve_unknown_fields: Flag,
+    /// description of the root schema (defaults to the doc comment of the spec struct)
+    doc: Option<String>,
+    #[darling(multiple, rename = "validation")]
+    validations: Vec<Validation>,
+    #[darling(multiple, rename = "annotation")]
+    annotations: Vec<KeyValue>,
+    #[darling(multiple, rename = "label")]
+    labels: Vec<KeyValue>,
     #[darling(multiple, rename = "category")]
     categories: Vec<String>,
     #[darling(multiple, rename = "shortname")]
     shortnames: Vec<String>,
     #[darling(multiple, rename = "printcolumn")]
     printcolums: Vec<String>,
-    scale: Option<String>,
+    scale: Option<Scale>,
     #[darling(default)]
     crates: Crates,
 }
 
+/// Values we can parse from #[kube(attrs)] on fields of the spec struct
+#[derive(Debug, FromField)]
+#[darling(attributes(kube), forward_attrs(serde))]
+struct KubeFieldAttrs {
+    ident: Option<Ident>,
+    ty: Type,
+    attrs: Vec<Attribute>,
+    print_column: Option<PrintColumn>,
+    #[darling(multiple, rename = "validation")]
+    validations: Vec<Validation>,
+}
+
+/// A printer column derived from a field, see `#[kube(print_column(...))]`
+#[derive(Debug, FromMeta)]
+struct PrintColumn {
+    name: String,
+    #[darling(rename = "type")]
+    type_: String,
+    description: Option<String>,
+    format: Option<String>,
+    priority: Option<i32>,
+}
+
+/// The scale subresource, see `#[kube(scale(...))]`
+#[derive(Debug)]
+enum Scale {
+    /// `scale = r#"json"#`
+    Raw(String),
+    /// `scale(spec_replicas = "...", status_replicas = "...", label_selector = "...")`
+    Paths {
+        spec_replicas: String,
+        status_replicas: String,
+        label_selector: Option<String>,
+    },
+}
+
+impl FromMeta for Scale {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self::Raw(value.to_string()))
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct Paths {
+            spec_replicas: String,
+            status_replicas: String,
+            label_selector: Option<String>,
+        }
+        let Paths {
+            spec_replicas,
+            status_replicas,
+            label_selector,
+        } = Paths::from_list(items)?;
+        Ok(Self::Paths {
+            spec_replicas,
+            status_replicas,
+            label_selector,
+        })
+    }
+}
+
+impl Scale {
+    // The json of a `CustomResourceSubresourceScale`
+    fn to_json(&self) -> String {
+        match self {
+            Scale::Raw(json) => json.clone(),
+            Scale::Paths {
+                spec_replicas,
+                status_replicas,
+                label_selector,
+            } => {
+                let mut json = serde_json::json!({
+                    "specReplicasPath": spec_replicas,
+                    "statusReplicasPath": status_replicas,
+                });
+                if let Some(label_selector) = label_selector {
+                    json["labelSelectorPath"] = label_selector.clone().into();
+                }
+                json.to_string()
+            }
+        }
+    }
+}
+
+/// A `("key", "value")` pair, see `#[kube(label("key", "value"))]`
+#[derive(Debug)]
+struct KeyValue {
+    key: String,
+    value: String,
+    /// Span of the key, for reporting duplicates
+    span: Span,
+}
+
+impl FromMeta for KeyValue {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        match items {
+            [NestedMeta::Lit(Lit::Str(key)), NestedMeta::Lit(Lit::Str(value))] => Ok(Self {
+                key: key.value(),
+                value: value.value(),
+                span: key.span(),
+            }),
+            _ => Err(darling::Error::custom(
+                r#"expected a ("key", "value") pair of strings"#,
+            )),
+        }
+    }
+}
+
+/// Only the versions of #[kube(attrs)], to tell a missing version apart from other errors
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(kube), allow_unknown_fields)]
+struct KubeVersions {
+    #[darling(multiple, rename = "version")]
+    versions: Vec<Version>,
+}
+
+/// A version of the crd, see `#[kube(version = "...")]`
+#[derive(Debug)]
+struct Version {
+    name: String,
+    served: Option<bool>,
+    storage: bool,
+}
+
+impl FromMeta for Version {
+    // `version = "v1"`
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self {
+            name: value.to_string(),
+            served: None,
+            storage: false,
+        })
+    }
+
+    // `version(name = "v1", served = false, storage)`
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct Full {
+            name: String,
+            served: Option<bool>,
+            storage: Flag,
+        }
+        let Full {
+            name,
+            served,
+            storage,
+        } = Full::from_list(items)?;
+        Ok(Self {
+            name,
+            served,
+            storage: storage.is_present(),
+        })
+    }
+}
+
+/// A CEL validation rule, see `#[kube(validation = "...")]`
+#[derive(Debug)]
+struct Validation {
+    rule: String,
+    message: Option<String>,
+}
+
+impl FromMeta for Validation {
+    // `validation = "rule"`
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self {
+            rule: value.to_string(),
+            message: None,
+        })
+    }
+
+    // `validation(rule = "rule", message = "message")`
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct Full {
+            rule: String,
+            message: Option<String>,
+        }
+        let Full { rule, message } = Full::from_list(items)?;
+        Ok(Self { rule, message })
+    }
+}
+
+impl Validation {
+    fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({ "rule": self.rule });
+        if let Some(message) = &self.message {
+            json["message"] = message.clone().into();
+        }
+        json
+    }
+}
+
 #[derive(Debug, FromMeta)]
 struct Crates {
     #[darling(default = "Self::default_kube_core")]
@@ -135,19 +349,37 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         }
     }
     let kube_attrs = match KubeAttrs::from_derive_input(&derive_input) {
-        Err(err) => return err.write_errors(),
-        Ok(attrs) => attrs,
+        Ok(attrs) if !attrs.versions.is_empty() => attrs,
+        result => {
+            let mut errors = result.err().into_iter().collect::<Vec<_>>();
+            let has_version = KubeVersions::from_derive_input(&derive_input)
+                .map_or(true, |attrs| !attrs.versions.is_empty());
+            if !has_version {
+                errors.push(darling::Error::missing_field("version"));
+            }
+            return darling::Error::multiple(errors).write_errors();
+        }
     };
 
     let KubeAttrs {
         group,
         kind,
         kind_struct,
-        version,
+        versions,
         namespaced,
+        served,
+        storage,
+        deprecated,
+        deprecation_warning,
         derives,
         schema: schema_mode,
         status,
+        conditions,
+        preserve_unknown_fields,
+        doc,
+        validations,
+        annotations,
+        labels,
         plural,
         singular,
         categories,
@@ -165,6 +397,14 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
             },
     } = kube_attrs;
 
+    let FieldAttrs {
+        printcolumns: field_printcolumns,
+        validations: field_validations,
+    } = match field_attrs(&derive_input.data, &derive_input.attrs) {
+        Err(err) => return err.write_errors(),
+        Ok(fields) => fields,
+    };
+
     let struct_name = kind_struct.unwrap_or_else(|| kind.clone());
     if derive_input.ident == struct_name {
         return syn::Error::new_spanned(
@@ -173,25 +413,51 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         )
         .to_compile_error();
     }
+    let docstr = doc
+        .or_else(|| doc_comment(&derive_input.attrs))
+        .unwrap_or_else(|| {
+            format!(
+                " Auto-generated derived type for {} via `CustomResource`",
+                derive_input.ident
+            )
+        });
     let visibility = derive_input.vis;
     let ident = derive_input.ident;
+    let generics = derive_input.generics;
+    if let Some(lifetime) = generics.lifetimes().next() {
+        return syn::Error::new_spanned(
+            lifetime,
+            "#[derive(CustomResource)] does not support lifetime parameters, the spec must be owned",
+        )
+        .to_compile_error();
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // 1. Create root object Foo and truncate name from FooSpec
 
     // Default visibility is `pub(crate)`
-    // Default generics is no generics (makes little sense to re-use CRD kind?)
+    // Generic parameters of the spec struct are forwarded to the root struct
     // We enforce metadata + spec's existence (always there)
     // => No default impl
     let rootident = Ident::new(&struct_name, Span::call_site());
     let rootident_str = rootident.to_string();
+    let root_ty = quote! { #rootident #ty_generics };
+    let spec_ty = quote! { #ident #ty_generics };
 
     // if status set, also add that
     let StatusInformation {
         field: status_field,
         default: status_default,
         impl_hasstatus,
-    } = process_status(&rootident, &status, &visibility, &kube_core);
+    } = process_status(&rootident, &generics, &status, &visibility, &kube_core);
     let has_status = status.is_some();
+    if conditions.is_present() && !has_status {
+        return syn::Error::new_spanned(
+            ident,
+            r#"#[kube(conditions)] requires a status struct to be set with `status = "..."`"#,
+        )
+        .to_compile_error();
+    }
     let serialize_status = if has_status {
         quote! {
             if let Some(status) = &self.status {
@@ -238,22 +504,39 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         derive_paths.push(syn::parse_quote! { #schemars::JsonSchema });
     }
 
-    let docstr = format!(" Auto-generated derived type for {} via `CustomResource`", ident);
+    // The spec of a generic root is only (de)serializable, defaultable and describable for some parameters
+    let bounded_where_clause = |bound: TokenStream| {
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #bound });
+        let where_clause = generics.where_clause;
+        quote! { #where_clause }
+    };
+    let serialize_where_clause = bounded_where_clause(quote! { #spec_ty: #serde::Serialize });
+    let default_where_clause = bounded_where_clause(quote! { #spec_ty: Default });
+    let crd_where_clause = if schema_mode.derive() {
+        bounded_where_clause(quote! { #root_ty: #schemars::JsonSchema })
+    } else {
+        quote! { #where_clause }
+    };
+
     let root_obj = quote! {
         #[doc = #docstr]
         #[automatically_derived]
         #[allow(missing_docs)]
         #[derive(#(#derive_paths),*)]
         #[serde(rename_all = "camelCase")]
-        #visibility struct #rootident {
+        #visibility struct #rootident #generics #where_clause {
             #schemars_skip
             #visibility metadata: #k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta,
-            #visibility spec: #ident,
+            #visibility spec: #spec_ty,
             #status_field
         }
-        impl #rootident {
+        impl #impl_generics #root_ty #where_clause {
             /// Spec based constructor for derived custom resource
-            pub fn new(name: &str, spec: #ident) -> Self {
+            pub fn new(name: &str, spec: #spec_ty) -> Self {
                 Self {
                     metadata: #k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
                         name: Some(name.to_string()),
@@ -264,12 +547,12 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
                 }
             }
         }
-        impl #serde::Serialize for #rootident {
+        impl #impl_generics #serde::Serialize for #root_ty #serialize_where_clause {
             fn serialize<S: #serde::Serializer>(&self, ser: S) -> #std::result::Result<S::Ok, S::Error> {
                 use #serde::ser::SerializeStruct;
                 let mut obj = ser.serialize_struct(#rootident_str, 4 + usize::from(#has_status_value))?;
-                obj.serialize_field("apiVersion", &<#rootident as #kube_core::Resource>::api_version(&()))?;
-                obj.serialize_field("kind", &<#rootident as #kube_core::Resource>::kind(&()))?;
+                obj.serialize_field("apiVersion", &<Self as #kube_core::Resource>::api_version(&()))?;
+                obj.serialize_field("kind", &<Self as #kube_core::Resource>::kind(&()))?;
                 obj.serialize_field("metadata", &self.metadata)?;
                 obj.serialize_field("spec", &self.spec)?;
                 #serialize_status
@@ -280,7 +563,119 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
 
     // 2. Implement Resource trait
     let name = singular.unwrap_or_else(|| kind.to_ascii_lowercase());
-    let plural = plural.unwrap_or_else(|| to_plural(&name));
+    let plural = match plural {
+        Some(plural) if !is_dns_label(&plural) => {
+            return syn::Error::new_spanned(
+                ident,
+                r#"#[kube(plural = "...")] must be a lowercase DNS label (a-z, 0-9 and '-', starting and ending with an alphanumeric character)"#,
+            )
+            .to_compile_error();
+        }
+        Some(plural) => plural,
+        None => {
+            let plural = to_plural(&name);
+            if !is_dns_label(&plural) {
+                return syn::Error::new_spanned(
+                    ident,
+                    format!(r#"#[derive(CustomResource)] inferred the invalid plural "{plural}", set a lowercase DNS label with `plural = "..."`"#),
+                )
+                .to_compile_error();
+            }
+            plural
+        }
+    };
+    // A single version is configured by the top level attributes, several by their own `version(...)` lists
+    let (version, served, storage, versions_json) = if let [version] = versions.as_slice() {
+        let served = version.served.or(served).unwrap_or(true);
+        let storage = version.storage || storage.unwrap_or(true);
+        (version.name.clone(), served, storage, None)
+    } else {
+        if served.is_some() || storage.is_some() || deprecated.is_present() {
+            return syn::Error::new_spanned(
+                ident,
+                r#"#[kube(served, storage, deprecated)] apply to a single version, use #[kube(version(name = "...", served = false, storage))] to configure several versions"#,
+            )
+            .to_compile_error();
+        }
+        let mut names = std::collections::HashSet::new();
+        if let Some(duplicate) = versions.iter().find(|v| !names.insert(&v.name)) {
+            return syn::Error::new_spanned(
+                ident,
+                format!(r#"duplicate #[kube(version)] "{}""#, duplicate.name),
+            )
+            .to_compile_error();
+        }
+        if let Some(unused) = versions.iter().find(|v| v.served == Some(false) && !v.storage) {
+            return syn::Error::new_spanned(
+                ident,
+                format!(
+                    r#"#[kube(version)] "{}" can be neither read nor written, remove it instead"#,
+                    unused.name
+                ),
+            )
+            .to_compile_error();
+        }
+        let mut storage_versions = versions.iter().filter(|v| v.storage);
+        let stored = match (storage_versions.next(), storage_versions.next()) {
+            (Some(stored), None) => stored,
+            (None, _) => {
+                return syn::Error::new_spanned(
+                    ident,
+                    r#"exactly one #[kube(version)] must be the storage version, mark it with #[kube(version(name = "...", storage))]"#,
+                )
+                .to_compile_error();
+            }
+            (Some(_), Some(_)) => {
+                return syn::Error::new_spanned(
+                    ident,
+                    r#"exactly one #[kube(version)] must be the storage version, but several are marked with `storage`"#,
+                )
+                .to_compile_error();
+            }
+        };
+        let served = stored.served.unwrap_or(true);
+        // Every version gets a copy of the storage version's schema, in the order they were declared
+        let all_versions = versions
+            .iter()
+            .map(|v| {
+                serde_json::json!({
+                    "name": v.name,
+                    "served": v.served.unwrap_or(true),
+                    "storage": v.storage,
+                })
+            })
+            .collect::<Vec<_>>();
+        (
+            stored.name.clone(),
+            served,
+            true,
+            Some(serde_json::to_string(&all_versions).unwrap()),
+        )
+    };
+    if !served && !storage {
+        return syn::Error::new_spanned(
+            ident,
+            r#"#[kube(served = false, storage = false)] is a version that can be neither read nor written, remove it instead"#,
+        )
+        .to_compile_error();
+    }
+    for (attr, pairs) in [("annotation", &annotations), ("label", &labels)] {
+        let mut keys = std::collections::HashSet::new();
+        if let Some(duplicate) = pairs.iter().find(|kv| !keys.insert(&kv.key)) {
+            return syn::Error::new(
+                duplicate.span,
+                format!(r#"duplicate #[kube({attr})] key "{}""#, duplicate.key),
+            )
+            .to_compile_error();
+        }
+    }
+    if deprecation_warning.is_some() && !deprecated.is_present() {
+        return syn::Error::new_spanned(
+            ident,
+            r#"#[kube(deprecation_warning = "...")] requires the version to be marked as #[kube(deprecated)]"#,
+        )
+        .to_compile_error();
+    }
     let (scope, scope_quote) = if namespaced {
         ("Namespaced", quote! { #kube_core::NamespaceResourceScope })
     } else {
@@ -289,7 +684,7 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
 
     let api_ver = format!("{}/{}", group, version);
     let impl_resource = quote! {
-        impl #kube_core::Resource for #rootident {
+        impl #impl_generics #kube_core::Resource for #root_ty #where_clause {
             type DynamicType = ();
             type Scope = #scope_quote;
 
@@ -326,7 +721,7 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
     // 3. Implement Default if requested
     let impl_default = if has_default {
         quote! {
-            impl Default for #rootident {
+            impl #impl_generics Default for #root_ty #default_where_clause {
                 fn default() -> Self {
                     Self {
                         metadata: #k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta::default(),
@@ -343,8 +738,15 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
     // 4. Implement CustomResource
 
     // Compute a bunch of crd props
-    let printers = format!("[ {} ]", printcolums.join(",")); // hacksss
-    let scale_code = if let Some(s) = scale { s } else { "".to_string() };
+    let printers = format!(
+        "[ {} ]",
+        printcolums
+            .into_iter()
+            .chain(field_printcolumns)
+            .collect::<Vec<_>>()
+            .join(",")
+    ); // hacksss
+    let scale_code = scale.as_ref().map(Scale::to_json).unwrap_or_default();
 
     // Ensure it generates for the correct CRD version (only v1 supported now)
     let apiext = quote! {
@@ -365,7 +767,16 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
     let categories_json = serde_json::to_string(&categories).unwrap();
     let short_json = serde_json::to_string(&shortnames).unwrap();
     let crd_meta_name = format!("{}.{}", plural, group);
-    let crd_meta = quote! { { "name": #crd_meta_name } };
+    let mut crd_meta = serde_json::json!({ "name": crd_meta_name });
+    if !annotations.is_empty() {
+        let annotations = annotations.into_iter().map(|kv| (kv.key, kv.value.into()));
+        crd_meta["annotations"] = serde_json::Map::from_iter(annotations).into();
+    }
+    if !labels.is_empty() {
+        let labels = labels.into_iter().map(|kv| (kv.key, kv.value.into()));
+        crd_meta["labels"] = serde_json::Map::from_iter(labels).into();
+    }
+    let crd_meta_json = crd_meta.to_string();
 
     let schemagen = if schema_mode.use_in_crd() {
         quote! {
@@ -391,8 +802,9 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
     let jsondata = quote! {
         #schemagen
 
-        let jsondata = #serde_json::json!({
-            "metadata": #crd_meta,
+        let crd_meta: #serde_json::Value = #serde_json::from_str(#crd_meta_json).expect("valid crd metadata json");
+        let mut jsondata = #serde_json::json!({
+            "metadata": crd_meta,
             "spec": {
                 "group": #group,
                 "scope": #scope,
@@ -405,8 +817,8 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
                 },
                 "versions": [{
                     "name": #version,
-                    "served": true,
-                    "storage": true,
+                    "served": #served,
+                    "storage": #storage,
                     "schema": {
                         "openAPIV3Schema": schema,
                     },
@@ -417,9 +829,89 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         });
     };
 
+    // Stop the apiserver from pruning fields that are not in the schema, while still generating it for known fields
+    let preserve_unknown = if preserve_unknown_fields.is_present() {
+        quote! {
+            if let Some(schema) = jsondata["spec"]["versions"][0]["schema"]["openAPIV3Schema"].as_object_mut() {
+                schema.insert("x-kubernetes-preserve-unknown-fields".into(), true.into());
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Have the apiserver send a warning to clients that use a deprecated version
+    let deprecate = if deprecated.is_present() {
+        let warning = match deprecation_warning {
+            Some(warning) => quote! { version.insert("deprecationWarning".into(), #warning.into()); },
+            None => quote! {},
+        };
+        quote! {
+            if let Some(version) = jsondata["spec"]["versions"][0].as_object_mut() {
+                version.insert("deprecated".into(), true.into());
+                #warning
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Share the finished version with every other #[kube(version)]
+    let copy_versions = match versions_json {
+        Some(versions_json) => quote! {
+            let versions: Vec<#serde_json::Value> = #serde_json::from_str(#versions_json).expect("valid version json");
+            let template = jsondata["spec"]["versions"][0].clone();
+            jsondata["spec"]["versions"] = versions
+                .into_iter()
+                .map(|fields| {
+                    let mut version = template.clone();
+                    if let (Some(version), Some(fields)) = (version.as_object_mut(), fields.as_object()) {
+                        version.extend(fields.clone());
+                    }
+                    version
+                })
+                .collect();
+        },
+        None => quote! {},
+    };
+
+    // Attach CEL rules to the spec schema, and to the schemas of individual spec fields
+    let spec_pointer = "/spec/versions/0/schema/openAPIV3Schema/properties/spec";
+    let validation_pointers = (!validations.is_empty())
+        .then(|| (spec_pointer.to_string(), validations))
+        .into_iter()
+        .chain(field_validations.into_iter().map(|(field, validations)| {
+            let field = field.replace('~', "~0").replace('/', "~1");
+            (format!("{spec_pointer}/properties/{field}"), validations)
+        }))
+        .map(|(pointer, validations)| {
+            let rules = validations.iter().map(Validation::to_json).collect::<Vec<_>>();
+            (pointer, serde_json::Value::from(rules))
+        })
+        .collect::<Vec<_>>();
+    let validate = if validation_pointers.is_empty() {
+        quote! {}
+    } else if schema_mode != SchemaMode::Derived {
+        return syn::Error::new_spanned(
+            ident,
+            r#"#[kube(validation)] requires a derived schema, remove `schema = "..."`"#,
+        )
+        .to_compile_error();
+    } else {
+        let validations_json = serde_json::to_string(&validation_pointers).unwrap();
+        quote! {
+            let validations: Vec<(String, #serde_json::Value)> = #serde_json::from_str(#validations_json).expect("valid validation rules json");
+            for (pointer, rules) in validations {
+                if let Some(schema) = jsondata.pointer_mut(&pointer).and_then(|s| s.as_object_mut()) {
+                    schema.insert("x-kubernetes-validations".into(), rules);
+                }
+            }
+        }
+    };
+
     // Implement the CustomResourceExt trait to allow users writing generic logic on top of them
     let impl_crd = quote! {
-        impl #extver::CustomResourceExt for #rootident {
+        impl #impl_generics #extver::CustomResourceExt for #root_ty #crd_where_clause {
 
             fn crd() -> #apiext::CustomResourceDefinition {
                 let columns : Vec<#apiext::CustomResourceColumnDefinition> = #serde_json::from_str(#printers).expect("valid printer column json");
@@ -444,6 +936,11 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
                 };
 
                 #jsondata
+                #preserve_unknown
+                #validate
+                #deprecate
+                #copy_versions
+                #kube_core::crd::sort_json_keys(&mut jsondata);
                 #serde_json::from_value(jsondata)
                     .expect("valid custom resource from #[kube(attrs..)]")
             }
@@ -462,7 +959,12 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         }
     };
 
-    let impl_hasspec = generate_hasspec(&ident, &rootident, &kube_core);
+    let impl_hasspec = generate_hasspec(&ident, &rootident, &generics, &kube_core);
+    let impl_hasconditions = if conditions.is_present() {
+        generate_hasconditions(&rootident, &generics, &kube_core, &k8s_openapi)
+    } else {
+        quote! {}
+    };
 
     // Concat output
     quote! {
@@ -472,6 +974,7 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
         #impl_crd
         #impl_hasspec
         #impl_hasstatus
+        #impl_hasconditions
     }
 }
 
@@ -483,23 +986,61 @@ pub(crate) fn derive(input: proc_macro2::TokenStream) -> proc_macro2::TokenStrea
 ///
 /// * `ident`: The identity (name) of the spec struct
 /// * `root ident`: The identity (name) of the main CRD struct (the one we generate in this macro)
+/// * `generics`: The generic parameters of the spec struct, shared by the main CRD struct
 /// * `kube_core`: The path stream for the analagous kube::core import location from users POV
-fn generate_hasspec(spec_ident: &Ident, root_ident: &Ident, kube_core: &Path) -> TokenStream {
+fn generate_hasspec(
+    spec_ident: &Ident,
+    root_ident: &Ident,
+    generics: &Generics,
+    kube_core: &Path,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote! {
-        impl #kube_core::object::HasSpec for #root_ident {
-            type Spec = #spec_ident;
+        impl #impl_generics #kube_core::object::HasSpec for #root_ident #ty_generics #where_clause {
+            type Spec = #spec_ident #ty_generics;
 
-            fn spec(&self) -> &#spec_ident {
+            fn spec(&self) -> &#spec_ident #ty_generics {
                 &self.spec
             }
 
-            fn spec_mut(&mut self) -> &mut #spec_ident {
+            fn spec_mut(&mut self) -> &mut #spec_ident #ty_generics {
                 &mut self.spec
             }
         }
     }
 }
 
+/// This generates the code for the `#kube_core::object::HasConditions` trait implementation.
+///
+/// It is opt-in via `#[kube(conditions)]`, and relies on the status struct having a `conditions` field.
+///
+/// # Arguments
+///
+/// * `root ident`: The identity (name) of the main CRD struct (the one we generate in this macro)
+/// * `generics`: The generic parameters of the spec struct, shared by the main CRD struct
+/// * `kube_core`: The path stream for the analagous kube::core import location from users POV
+/// * `k8s_openapi`: The path stream for the k8s_openapi import location from users POV
+fn generate_hasconditions(
+    root_ident: &Ident,
+    generics: &Generics,
+    kube_core: &Path,
+    k8s_openapi: &Path,
+) -> TokenStream {
+    let condition = quote! { #k8s_openapi::apimachinery::pkg::apis::meta::v1::Condition };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #kube_core::object::HasConditions for #root_ident #ty_generics #where_clause {
+            fn conditions(&self) -> &[#condition] {
+                self.status.as_ref().map(|s| s.conditions.as_slice()).unwrap_or_default()
+            }
+
+            fn conditions_mut(&mut self) -> &mut Vec<#condition> {
+                &mut self.status.get_or_insert_with(Default::default).conditions
+            }
+        }
+    }
+}
+
 struct StatusInformation {
     /// The code to be used for the field in the main struct
     field: TokenStream,
@@ -516,6 +1057,7 @@ struct StatusInformation {
 /// # Arguments
 ///
 /// * `root ident`: The identity (name) of the main CRD struct (the one we generate in this macro)
+/// * `generics`: The generic parameters of the spec struct, shared by the main CRD struct
 /// * `status`: The optional name of the `status` struct to use
 /// * `visibility`: Desired visibility of the generated field
 /// * `kube_core`: The path stream for the analagous kube::core import location from users POV
@@ -523,12 +1065,14 @@ struct StatusInformation {
 /// returns: A `StatusInformation` struct
 fn process_status(
     root_ident: &Ident,
+    generics: &Generics,
     status: &Option<String>,
     visibility: &Visibility,
     kube_core: &Path,
 ) -> StatusInformation {
     if let Some(status_name) = &status {
         let ident = format_ident!("{}", status_name);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         StatusInformation {
             field: quote! {
                 #[serde(skip_serializing_if = "Option::is_none")]
@@ -536,7 +1080,7 @@ fn process_status(
             },
             default: quote! { status: None, },
             impl_hasstatus: quote! {
-                impl #kube_core::object::HasStatus for #root_ident {
+                impl #impl_generics #kube_core::object::HasStatus for #root_ident #ty_generics #where_clause {
 
                     type Status = #ident;
 
@@ -560,6 +1104,212 @@ fn process_status(
     }
 }
 
+/// What the `#[kube(...)]` attributes on fields of the spec struct add to the crd
+#[derive(Default)]
+struct FieldAttrs {
+    /// printer columns as json
+    printcolumns: Vec<String>,
+    /// validation rules, by serialized field name
+    validations: Vec<(String, Vec<Validation>)>,
+}
+
+/// This collects the printer columns and validation rules declared on spec fields with
+/// `#[kube(print_column(...))]` and `#[kube(validation = "...")]`.
+///
+/// Both refer to the serialized name of the field under `.spec`,
+/// taking `#[serde(rename)]` and `#[serde(rename_all)]` into account.
+///
+/// returns: The columns as json and the rules of each field, in field order
+fn field_attrs(data: &Data, attrs: &[Attribute]) -> darling::Result<FieldAttrs> {
+    let mut result = FieldAttrs::default();
+    let fields = match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Ok(result),
+        },
+        _ => return Ok(result),
+    };
+    let rename_all = serde_attr(attrs, "rename_all");
+    let mut errors = darling::Error::accumulator();
+    for field in fields {
+        let attrs = match errors.handle(KubeFieldAttrs::from_field(field)) {
+            Some(attrs) => attrs,
+            None => continue,
+        };
+        let field_name = match serde_attr(&attrs.attrs, "rename") {
+            Some(rename) => rename,
+            None => {
+                let ident = attrs.ident.as_ref().expect("named field").to_string();
+                let ident = ident.trim_start_matches("r#");
+                match &rename_all {
+                    Some(rule) => apply_rename_rule(ident, rule),
+                    None => ident.to_string(),
+                }
+            }
+        };
+        // Flattened and skipped fields have no property of their own in the schema to hold the rules
+        let unschematized = ["flatten", "skip"]
+            .into_iter()
+            .find(|flag| serde_flag(&attrs.attrs, flag));
+        if let (Some(flag), false) = (unschematized, attrs.validations.is_empty()) {
+            errors.push(
+                darling::Error::custom(format!(
+                    "#[kube(validation)] cannot be used on a #[serde({flag})] field, as it has no schema of its own"
+                ))
+                .with_span(attrs.ident.as_ref().expect("named field")),
+            );
+        } else if !attrs.validations.is_empty() {
+            result.validations.push((field_name.clone(), attrs.validations));
+        }
+        let column = match attrs.print_column {
+            Some(column) => column,
+            None => continue,
+        };
+        if !matches!(
+            column.type_.as_str(),
+            "integer" | "number" | "string" | "boolean" | "date"
+        ) {
+            errors.push(
+                darling::Error::custom(format!(
+                    "unknown printer column type `{}`, expected one of integer, number, string, boolean, or date",
+                    column.type_
+                ))
+                .with_span(&attrs.ty));
+            continue;
+        }
+        if !is_scalar_type(&attrs.ty) {
+            errors.push(
+                darling::Error::custom("#[kube(print_column)] requires a field that serializes to a scalar")
+                    .with_span(&attrs.ty),
+            );
+            continue;
+        }
+        let mut json = serde_json::json!({
+            "name": column.name,
+            "type": column.type_,
+            "jsonPath": format!(".spec.{}", field_name),
+        });
+        if let Some(description) = column.description {
+            json["description"] = description.into();
+        }
+        if let Some(format) = column.format {
+            json["format"] = format.into();
+        }
+        if let Some(priority) = column.priority {
+            json["priority"] = priority.into();
+        }
+        result.printcolumns.push(json.to_string());
+    }
+    errors.finish_with(result)
+}
+
+// The doc comment of an item, if any.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(nv)) => match nv.lit {
+                Lit::Str(line) => Some(line.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let doc = lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let doc = doc.trim();
+    (!doc.is_empty()).then(|| doc.to_string())
+}
+
+// The value of a `#[serde(key = "value")]` attribute, if any.
+fn serde_attr(attrs: &[Attribute], key: &str) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident(key) => match nv.lit {
+                Lit::Str(value) => Some(value.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+// Whether a serde attribute like `#[serde(flatten)]` is set
+fn serde_flag(attrs: &[Attribute], key: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident(key)))
+}
+
+// Applies a serde `rename_all` rule to a snake_case field name, mirroring serde's own behaviour.
+fn apply_rename_rule(field: &str, rule: &str) -> String {
+    match rule {
+        "lowercase" => field.to_ascii_lowercase(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        "camelCase" | "PascalCase" => {
+            let mut renamed = String::new();
+            let mut capitalize = rule == "PascalCase";
+            for c in field.chars() {
+                if c == '_' {
+                    capitalize = true;
+                } else if capitalize {
+                    renamed.push(c.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    renamed.push(c);
+                }
+            }
+            renamed
+        }
+        _ => field.to_string(),
+    }
+}
+
+// Whether a field type can serialize to a scalar, rejecting the collection types that certainly don't.
+fn is_scalar_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "Option" || segment.ident == "Box" => {
+                match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args.args.iter().all(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => is_scalar_type(ty),
+                        _ => true,
+                    }),
+                    _ => true,
+                }
+            }
+            Some(segment) => !matches!(
+                segment.ident.to_string().as_str(),
+                "Vec" | "VecDeque" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet" | "IndexMap"
+            ),
+            None => true,
+        },
+        Type::Reference(reference) => is_scalar_type(&reference.elem),
+        Type::Group(group) => is_scalar_type(&group.elem),
+        Type::Paren(paren) => is_scalar_type(&paren.elem),
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => false,
+        _ => true,
+    }
+}
+
 // Simple pluralizer.
 // Duplicating the code from kube (without special casing) because it's simple enough.
 // Irregular plurals must be explicitly specified.
@@ -591,6 +1341,17 @@ fn to_plural(word: &str) -> String {
     format!("{}s", word)
 }
 
+// Whether `name` is a valid RFC 1123 label, as required by the apiserver for resource plurals.
+fn is_dns_label(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 63
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -606,8 +1367,60 @@ mod tests {
         let input = syn::parse2(input).unwrap();
         let kube_attrs = KubeAttrs::from_derive_input(&input).unwrap();
         assert_eq!(kube_attrs.group, "clux.dev".to_string());
-        assert_eq!(kube_attrs.version, "v1".to_string());
+        assert_eq!(kube_attrs.versions[0].name, "v1".to_string());
         assert_eq!(kube_attrs.kind, "Foo".to_string());
         assert_eq!(kube_attrs.namespaced, true);
     }
+
+    #[test]
+    fn test_field_printcolumns() {
+        let input = quote! {
+            #[derive(CustomResource, Serialize, Deserialize, Debug, PartialEq, Clone, JsonSchema)]
+            #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+            #[serde(rename_all = "camelCase")]
+            struct FooSpec {
+                #[kube(print_column(name = "Replicas", type = "integer", priority = 1))]
+                desired_replicas: Option<i32>,
+                #[serde(rename = "img")]
+                #[kube(print_column(name = "Image", type = "string"))]
+                image: String,
+                tags: Vec<String>,
+            }
+        };
+        let input: DeriveInput = syn::parse2(input).unwrap();
+        let columns = field_attrs(&input.data, &input.attrs)
+            .unwrap()
+            .printcolumns
+            .iter()
+            .map(|column| serde_json::from_str::<serde_json::Value>(column).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec![
+            serde_json::json!({"jsonPath": ".spec.desiredReplicas", "name": "Replicas", "priority": 1, "type": "integer"}),
+            serde_json::json!({"jsonPath": ".spec.img", "name": "Image", "type": "string"}),
+        ]);
+    }
+
+    #[test]
+    fn test_field_printcolumns_rejects_non_scalars() {
+        let input = quote! {
+            struct FooSpec {
+                #[kube(print_column(name = "Tags", type = "string"))]
+                tags: Option<Vec<String>>,
+            }
+        };
+        let input: DeriveInput = syn::parse2(input).unwrap();
+        assert!(field_attrs(&input.data, &input.attrs).is_err());
+    }
+
+    #[test]
+    fn test_is_dns_label() {
+        assert!(is_dns_label("foos"));
+        assert!(is_dns_label("v1-foo2s"));
+        assert!(!is_dns_label(""));
+        assert!(!is_dns_label("Foos"));
+        assert!(!is_dns_label("foo_bars"));
+        assert!(!is_dns_label("foo.bars"));
+        assert!(!is_dns_label("-foos"));
+        assert!(!is_dns_label(&"a".repeat(64)));
+    }
 }
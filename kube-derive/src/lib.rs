@@ -63,6 +63,12 @@ mod custom_resource;
 /// ## `#[kube(version = "v1")]`
 /// Your cr api version. The part after the slash in the top level `apiVersion` key.
 ///
+/// Repeat it to serve the same schema under several versions, marking exactly one of them as the storage version:
+/// `#[kube(version = "v1alpha1", version(name = "v1", storage))]`. Only the storage version can stop being served, with
+/// `version(name = "v1", served = false, storage)`. The generated type uses the storage version, and a derive
+/// with no or several storage versions fails to compile. Versions with different schemas are derived separately,
+/// and combined with [`merge_crds`](https://docs.rs/kube/latest/kube/core/crd/fn.merge_crds.html).
+///
 /// ## `#[kube(kind = "Kind")]`
 /// Name of your kind and your generated root type.
 ///
@@ -77,6 +83,19 @@ mod custom_resource;
 /// ## `#[kube(namespaced)]`
 /// To specify that this is a namespaced resource rather than cluster level.
 ///
+/// ## `#[kube(served = false)]`
+/// Stop serving this version, e.g. to phase out a deprecated version of a multi-version crd. Defaults to `true`.
+///
+/// ## `#[kube(storage = false)]`
+/// Whether objects are persisted in this version. Defaults to `true`, as the generated crd only has one version.
+/// Note that [`merge_crds`](https://docs.rs/kube/latest/kube/core/crd/fn.merge_crds.html) picks the storage version itself.
+/// A version with both `served = false` and `storage = false` is rejected at compile time.
+/// With several `#[kube(version)]`s, `served`, `storage` and `deprecated` are rejected in favour of `version(...)`.
+///
+/// ## `#[kube(deprecated)]`
+/// Mark this version as deprecated, so that the apiserver returns a warning header to clients using it,
+/// which `kubectl` prints. Combine with `#[kube(deprecation_warning = "use v2 instead")]` to replace the default warning.
+///
 /// ## `#[kube(struct = "StructName")]`
 /// Customize the name of the generated root struct (defaults to `kind`).
 ///
@@ -100,10 +119,37 @@ mod custom_resource;
 /// Adds a status struct to the top level generated type and enables the status
 /// subresource in your crd.
 ///
+/// ## `#[kube(conditions)]`
+/// Implements [`kube::core::object::HasConditions`] on the generated type for typed access to `status.conditions`.
+/// Requires `status` to be set, and the status struct to implement `Default` and have a `conditions: Vec<Condition>` field,
+/// where `Condition` is `k8s_openapi::apimachinery::pkg::apis::meta::v1::Condition`.
+///
 /// ## `#[kube(derive = "Trait")]`
 /// Adding `#[kube(derive = "PartialEq")]` is required if you want your generated
 /// top level type to be able to `#[derive(PartialEq)]`
 ///
+/// `#[kube(derive = "Default")]` generates an `impl Default` with empty metadata
+/// (`ObjectMeta::default()`, so no name, namespace, labels or annotations), the `Default` of the spec struct,
+/// and no status. The spec struct must implement `Default`.
+/// To construct a named object, use the generated `new(name, spec)` constructor and then set any other metadata:
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use kube_derive::CustomResource;
+/// # use schemars::JsonSchema;
+/// #[derive(CustomResource, Serialize, Deserialize, Default, Debug, Clone, JsonSchema)]
+/// #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced, derive = "Default")]
+/// struct FooSpec {
+///     replicas: i32,
+/// }
+///
+/// let empty = Foo::default();
+/// assert_eq!(empty.metadata.name, None);
+///
+/// let mut named = Foo::new("my-foo", FooSpec::default());
+/// named.metadata.labels = Some([("app".to_string(), "foo".to_string())].into());
+/// ```
+///
 /// ## `#[kube(schema = "mode")]`
 /// Defines whether the `JsonSchema` of the top level generated type should be used when generating a `CustomResourceDefinition`.
 ///
@@ -120,15 +166,86 @@ mod custom_resource;
 /// NOTE: `CustomResourceDefinition`s require a schema. If `schema = "disabled"` then
 /// `Self::crd()` will not be installable into the cluster as-is.
 ///
+/// ## `#[kube(doc = "description")]`
+/// Sets the description of the generated type, which becomes the `description` of the root schema in the crd
+/// and is shown by `kubectl explain`. Defaults to the doc comment of the spec struct, if it has one.
+///
+/// ## `#[kube(preserve_unknown_fields)]`
+/// Sets `x-kubernetes-preserve-unknown-fields: true` at the root of the generated schema,
+/// so that the apiserver stores fields that are not part of the schema rather than pruning them.
+/// The schema is still generated (and validated against) for known fields.
+///
+/// ## `#[kube(validation = "rule")]`
+/// Adds a [CEL validation rule](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#validation-rules)
+/// to `x-kubernetes-validations` of the spec schema, where `self` refers to the spec.
+/// Use `#[kube(validation(rule = "rule", message = "message"))]` to also set the message shown when the rule fails.
+/// Can be specified multiple times, and on fields of the spec struct, where `self` refers to the field.
+/// Fields without their own property in the schema (`#[serde(flatten)]` and `#[serde(skip)]` fields)
+/// cannot have rules, and fail to compile.
+///
+/// Validation rules require a derived schema, and are enforced from Kubernetes 1.25 onwards.
+/// They are only kept in `Self::crd()` with a `k8s-openapi` version feature of `v1_23` or later.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use kube_derive::CustomResource;
+/// # use schemars::JsonSchema;
+/// #[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+/// #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+/// #[kube(validation(rule = "self.replicas <= self.maxReplicas", message = "replicas exceed maxReplicas"))]
+/// #[serde(rename_all = "camelCase")]
+/// struct FooSpec {
+///     replicas: i32,
+///     max_replicas: i32,
+///     #[kube(validation = "self == oldSelf")]
+///     storage_class: String, // immutable
+/// }
+/// ```
+///
 /// ## `#[kube(scale = r#"json"#)]`
 /// Allow customizing the scale struct for the [scale subresource](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#subresources).
 ///
+/// ## `#[kube(scale(spec_replicas = ".spec.replicas", status_replicas = ".status.replicas"))]`
+/// Structured alternative to the above, building the scale subresource from its json paths.
+/// An optional `label_selector = ".status.selector"` sets the `labelSelectorPath`.
+///
 /// ## `#[kube(printcolumn = r#"json"#)]`
 /// Allows adding straight json to [printcolumns](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#additional-printer-columns).
+/// All properties of a printer column are kept, including `priority` (`1` to only show the column with `-o wide`) and `format`.
+///
+/// ## `#[kube(print_column(name = "Replicas", type = "integer"))]` on a spec field
+/// Adds a printer column for the annotated field of the spec struct, with its `jsonPath` derived from the
+/// field's serialized name (respecting `#[serde(rename)]` and `#[serde(rename_all)]`).
+/// Optional `description`, `format`, and `priority` keys map directly to the printer column properties.
+/// These columns are listed after any top level `printcolumn` attributes.
+/// The field must serialize to a scalar, so collections such as `Vec` or `BTreeMap` are rejected at compile time.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use kube_derive::CustomResource;
+/// # use schemars::JsonSchema;
+/// #[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+/// #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+/// #[serde(rename_all = "camelCase")]
+/// struct FooSpec {
+///     #[kube(print_column(name = "Replicas", type = "integer"))]
+///     desired_replicas: i32, // printed from .spec.desiredReplicas
+/// }
+/// ```
 ///
 /// ## `#[kube(shortname = "sn")]`
 /// Add a single shortname to the generated crd.
 ///
+/// ## `#[kube(category = "apps")]`
+/// Add a single category to `crd.spec.names.categories`, so that e.g. `kubectl get apps` includes this kind.
+/// Can be specified multiple times.
+///
+/// ## `#[kube(label("key", "value"))]`
+/// Add a single label to the metadata of the generated crd. Can be specified multiple times, with distinct keys.
+///
+/// ## `#[kube(annotation("key", "value"))]`
+/// Add a single annotation to the metadata of the generated crd. Can be specified multiple times, with distinct keys.
+///
 /// ## Example with all properties
 ///
 /// ```rust
@@ -149,6 +266,7 @@ mod custom_resource;
 ///     singular = "foot",
 ///     plural = "feetz",
 ///     shortname = "f",
+///     category = "clux",
 ///     scale = r#"{"specReplicasPath":".spec.replicas", "statusReplicasPath":".status.replicas"}"#,
 ///     printcolumn = r#"{"name":"Spec", "type":"string", "description":"name of foo", "jsonPath":".spec.name"}"#
 /// )]
@@ -217,6 +335,12 @@ mod custom_resource;
 /// - **adding validation** via [validator crate](https://github.com/Keats/validator) is supported from `schemars` >= [`0.8.5`](https://github.com/GREsau/schemars/blob/master/CHANGELOG.md#085---2021-09-20)
 /// - **generating rust code from schemas** can be done via [kopium](https://github.com/kube-rs/kopium) and is supported on stable crds (> 1.16 kubernetes)
 ///
+/// ## Generic specs
+/// Type parameters of the spec struct are carried over to the generated type, so that a family of similar
+/// resources can share one spec definition, e.g. `FooSpec<T>` generates `Foo<T>`. All instantiations share the
+/// `group`, `version` and `kind` of the attributes, so pick one instantiation per cluster when calling `crd()`.
+/// Lifetime parameters are not supported.
+///
 /// ## Validation Caveats
 /// The supported **`#[validate]` attrs also exist as `#[schemars]` attrs** so you can use those directly if you do not require the validation to run client-side (in your code).
 /// Otherwise, you should `#[derive(Validate)]` on your struct to have both server-side (kubernetes) and client-side validation.
@@ -283,6 +407,7 @@ mod custom_resource;
 /// [`kube::Resource`]: https://docs.rs/kube/*/kube/trait.Resource.html
 /// [`kube::core::ApiResource`]: https://docs.rs/kube/*/kube/core/struct.ApiResource.html
 /// [`kube::CustomResourceExt`]: https://docs.rs/kube/*/kube/trait.CustomResourceExt.html
+/// [`kube::core::object::HasConditions`]: https://docs.rs/kube/*/kube/core/object/trait.HasConditions.html
 #[proc_macro_derive(CustomResource, attributes(kube))]
 pub fn derive_custom_resource(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     custom_resource::derive(proc_macro2::TokenStream::from(input)).into()
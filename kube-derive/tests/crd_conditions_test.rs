@@ -0,0 +1,70 @@
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use kube::core::object::{HasConditions, HasStatus};
+use kube_derive::CustomResource;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Database",
+    namespaced,
+    status = "DatabaseStatus",
+    conditions,
+    schema = "disabled"
+)]
+struct DatabaseSpec {
+    replicas: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct DatabaseStatus {
+    conditions: Vec<Condition>,
+}
+
+fn condition(type_: &str, status: &str, reason: &str, secs: i64) -> Condition {
+    use chrono::{TimeZone, Utc};
+    Condition {
+        type_: type_.into(),
+        status: status.into(),
+        reason: reason.into(),
+        message: String::new(),
+        observed_generation: None,
+        last_transition_time: Time(Utc.timestamp_opt(secs, 0).unwrap()),
+    }
+}
+
+#[test]
+fn test_conditions_on_unset_status() {
+    let db = Database::new("db", DatabaseSpec { replicas: 1 });
+    assert!(db.conditions().is_empty());
+    assert!(db.get_condition("Ready").is_none());
+}
+
+#[test]
+fn test_set_condition() {
+    let mut db = Database::new("db", DatabaseSpec { replicas: 1 });
+    db.set_condition(condition("Ready", "False", "Provisioning", 1));
+    assert!(db.status().is_some());
+    assert_eq!(db.get_condition("Ready").unwrap().reason, "Provisioning");
+
+    // Same status keeps the original transition time
+    db.set_condition(condition("Ready", "False", "StillProvisioning", 2));
+    let ready = db.get_condition("Ready").unwrap();
+    assert_eq!(ready.reason, "StillProvisioning");
+    assert_eq!(ready.last_transition_time.0.timestamp(), 1);
+
+    // Changed status updates the transition time
+    db.set_condition(condition("Ready", "True", "Provisioned", 3));
+    let ready = db.get_condition("Ready").unwrap();
+    assert_eq!(ready.status, "True");
+    assert_eq!(ready.last_transition_time.0.timestamp(), 3);
+
+    db.set_condition(condition("Degraded", "False", "Healthy", 3));
+    assert_eq!(db.conditions().len(), 2);
+
+    let removed = db.remove_condition("Degraded").unwrap();
+    assert_eq!(removed.reason, "Healthy");
+    assert_eq!(db.conditions().len(), 1);
+    assert!(db.remove_condition("Degraded").is_none());
+}
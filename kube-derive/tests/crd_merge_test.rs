@@ -0,0 +1,88 @@
+// Multiple versions of a kind are derived in separate modules and combined with `merge_crds`,
+// or derived once when they share a schema
+use kube::core::{crd::merge_crds, CustomResourceExt};
+
+mod v1alpha1 {
+    use kube_derive::CustomResource;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+    #[kube(group = "clux.dev", version = "v1alpha1", kind = "Foo", namespaced)]
+    pub struct FooSpec {
+        pub name: String,
+    }
+}
+
+mod v1 {
+    use kube_derive::CustomResource;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+    #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+    pub struct FooSpec {
+        pub name: String,
+        pub replicas: Option<i32>,
+    }
+}
+
+mod shared {
+    use kube_derive::CustomResource;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+    #[kube(
+        group = "clux.dev",
+        version = "v1alpha1",
+        version(name = "v1", storage),
+        version = "v2beta1",
+        kind = "Bar",
+        namespaced
+    )]
+    pub struct BarSpec {
+        pub name: String,
+    }
+}
+
+#[test]
+fn merged_versions_have_a_single_storage_version() {
+    let crd = merge_crds(vec![v1alpha1::Foo::crd(), v1::Foo::crd()], "v1").unwrap();
+    assert_eq!(crd.metadata.name.as_deref(), Some("foos.clux.dev"));
+    let versions = crd
+        .spec
+        .versions
+        .iter()
+        .map(|v| (v.name.as_str(), v.served, v.storage))
+        .collect::<Vec<_>>();
+    assert_eq!(versions, vec![("v1", true, true), ("v1alpha1", true, false)]);
+}
+
+#[test]
+fn merging_requires_the_storage_version() {
+    assert!(merge_crds(vec![v1alpha1::Foo::crd(), v1::Foo::crd()], "v2").is_err());
+}
+
+#[test]
+fn derived_versions_share_the_schema() {
+    use kube::core::Resource;
+    assert_eq!(shared::Bar::api_version(&()), "clux.dev/v1");
+    let crd = shared::Bar::crd();
+    let versions = crd
+        .spec
+        .versions
+        .iter()
+        .map(|v| (v.name.as_str(), v.served, v.storage))
+        .collect::<Vec<_>>();
+    assert_eq!(versions, vec![
+        ("v1alpha1", true, false),
+        ("v1", true, true),
+        ("v2beta1", true, false)
+    ]);
+    assert!(crd
+        .spec
+        .versions
+        .iter()
+        .all(|v| v.schema == crd.spec.versions[1].schema));
+}
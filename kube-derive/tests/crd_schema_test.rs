@@ -57,6 +57,110 @@ pub struct FlatteningSpec {
     arbitrary: HashMap<String, serde_json::Value>,
 }
 
+/// A bar managed by the bar operator
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Bar",
+    label("app.kubernetes.io/managed-by", "bar-operator"),
+    label("tier", "backend"),
+    annotation("clux.dev/owner", "team-bar"),
+    served = false,
+    deprecated,
+    deprecation_warning = "bars are closed, use baz instead"
+)]
+pub struct BarSpec {
+    foo: String,
+}
+
+/// Only the Rust docs
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Baz",
+    doc = "A baz managed by the baz operator",
+    printcolumn = r#"{"name":"Created", "type":"date", "format":"date-time", "priority":1, "jsonPath":".metadata.creationTimestamp"}"#
+)]
+pub struct BazSpec {
+    foo: String,
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Preserving",
+    preserve_unknown_fields
+)]
+pub struct PreservingSpec {
+    foo: String,
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Validated",
+    validation = "self.replicas <= self.maxReplicas",
+    validation(rule = "self.maxReplicas <= 10", message = "too many replicas")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatedSpec {
+    replicas: i32,
+    max_replicas: i32,
+    #[kube(validation = "self == oldSelf")]
+    #[serde(rename = "storage/class")]
+    storage_class: String,
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Scaled",
+    status = "ScaledStatus",
+    scale(
+        spec_replicas = ".spec.replicas",
+        status_replicas = ".status.replicas",
+        label_selector = ".status.selector"
+    )
+)]
+pub struct ScaledSpec {
+    replicas: i32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+pub struct ScaledStatus {
+    replicas: i32,
+    selector: String,
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug)]
+#[kube(group = "clux.dev", version = "v1", kind = "Schemaless", schema = "disabled")]
+pub struct SchemalessSpec {
+    foo: String,
+}
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Wrapper",
+    namespaced,
+    status = "WrapperStatus",
+    derive = "Default"
+)]
+pub struct WrapperSpec<T> {
+    inner: T,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema)]
+pub struct WrapperStatus {
+    ready: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[allow(clippy::enum_variant_names)]
@@ -75,6 +179,19 @@ fn test_crd_name() {
     assert_eq!("foos.clux.dev", Foo::crd_name());
 }
 
+#[test]
+fn test_crd_is_usable_generically() {
+    use kube::core::CustomResourceExt;
+    fn crd_names<K: CustomResourceExt>() -> (&'static str, Option<String>) {
+        (K::crd_name(), K::crd().metadata.name)
+    }
+    assert_eq!(
+        crd_names::<Foo>(),
+        ("foos.clux.dev", Some("foos.clux.dev".into()))
+    );
+    assert_eq!(crd_names::<Flattening>().0, "flattenings.clux.dev");
+}
+
 #[test]
 fn test_shortnames() {
     use kube::core::CustomResourceExt;
@@ -261,3 +378,184 @@ fn flattening() {
     assert_eq!(spec.x_kubernetes_preserve_unknown_fields, Some(true));
     assert_eq!(spec.additional_properties, None);
 }
+
+#[test]
+fn preserve_unknown_fields() {
+    use kube::core::CustomResourceExt;
+    let crd = serde_json::to_value(Preserving::crd()).unwrap();
+    let schema = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"];
+    assert_eq!(schema["x-kubernetes-preserve-unknown-fields"], true);
+    assert_eq!(
+        schema["properties"]["spec"]["properties"]["foo"]["type"],
+        "string"
+    );
+
+    let crd = serde_json::to_value(Foo::crd()).unwrap();
+    let schema = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"];
+    assert!(schema.get("x-kubernetes-preserve-unknown-fields").is_none());
+}
+
+#[test]
+fn crd_labels_and_annotations() {
+    use kube::core::CustomResourceExt;
+    let crd = Bar::crd();
+    assert_eq!(crd.metadata.name.as_deref(), Some("bars.clux.dev"));
+    assert_eq!(
+        crd.metadata.labels,
+        Some(
+            [
+                (
+                    "app.kubernetes.io/managed-by".to_string(),
+                    "bar-operator".to_string()
+                ),
+                ("tier".to_string(), "backend".to_string()),
+            ]
+            .into()
+        )
+    );
+    assert_eq!(
+        crd.metadata.annotations,
+        Some([("clux.dev/owner".to_string(), "team-bar".to_string())].into())
+    );
+    assert_eq!(Foo::crd().metadata.labels, None);
+}
+
+#[test]
+fn version_served_and_storage() {
+    use kube::core::CustomResourceExt;
+    let version = &Bar::crd().spec.versions[0];
+    assert!(!version.served);
+    assert!(version.storage);
+    let version = &Foo::crd().spec.versions[0];
+    assert!(version.served);
+    assert!(version.storage);
+}
+
+#[test]
+fn version_deprecation() {
+    use kube::core::CustomResourceExt;
+    let version = &Bar::crd().spec.versions[0];
+    assert_eq!(version.deprecated, Some(true));
+    assert_eq!(
+        version.deprecation_warning.as_deref(),
+        Some("bars are closed, use baz instead")
+    );
+    let version = &Foo::crd().spec.versions[0];
+    assert_eq!(version.deprecated, None);
+    assert_eq!(version.deprecation_warning, None);
+}
+
+#[test]
+fn generic_spec() {
+    use kube::core::{object::HasSpec, CustomResourceExt, Resource};
+    let wrapper = Wrapper::new("w", WrapperSpec { inner: 3_u32 });
+    assert_eq!(wrapper.spec().inner, 3);
+    assert_eq!(Wrapper::<String>::kind(&()), "Wrapper");
+    assert_eq!(Wrapper::<String>::default().spec.inner, "");
+    assert_eq!(
+        serde_json::to_value(&wrapper).unwrap(),
+        serde_json::json!({
+            "apiVersion": "clux.dev/v1",
+            "kind": "Wrapper",
+            "metadata": { "name": "w" },
+            "spec": { "inner": 3 },
+        })
+    );
+
+    let crd = serde_json::to_value(Wrapper::<String>::crd()).unwrap();
+    let spec = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"]["properties"]["spec"];
+    assert_eq!(spec["properties"]["inner"]["type"], "string");
+    let crd = serde_json::to_value(Wrapper::<u32>::crd()).unwrap();
+    let spec = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"]["properties"]["spec"];
+    assert_eq!(spec["properties"]["inner"]["type"], "integer");
+}
+
+#[test]
+fn printcolumn_priority_and_format() {
+    use kube::core::CustomResourceExt;
+    let crd = Baz::crd();
+    let columns = crd.spec.versions[0].additional_printer_columns.as_ref().unwrap();
+    assert_eq!(columns.len(), 1);
+    assert_eq!(columns[0].name, "Created");
+    assert_eq!(columns[0].priority, Some(1));
+    assert_eq!(columns[0].format.as_deref(), Some("date-time"));
+}
+
+#[test]
+fn schema_matches_crd() {
+    use kube::core::CustomResourceExt;
+    let schema = Foo::schema().unwrap();
+    assert_eq!(
+        Some(schema),
+        Foo::crd().spec.versions[0]
+            .schema
+            .clone()
+            .unwrap()
+            .open_api_v3_schema
+    );
+    assert_eq!(Schemaless::schema(), None);
+}
+
+#[test]
+fn structured_scale() {
+    use kube::core::CustomResourceExt;
+    let crd = serde_json::to_value(Scaled::crd()).unwrap();
+    assert_eq!(
+        crd["spec"]["versions"][0]["subresources"],
+        serde_json::json!({
+            "status": {},
+            "scale": {
+                "specReplicasPath": ".spec.replicas",
+                "statusReplicasPath": ".status.replicas",
+                "labelSelectorPath": ".status.selector",
+            },
+        })
+    );
+}
+
+#[test]
+fn validation_rules() {
+    use kube::core::CustomResourceExt;
+    let crd = serde_json::to_value(Validated::crd()).unwrap();
+    let spec = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"]["properties"]["spec"];
+    assert_eq!(
+        spec["x-kubernetes-validations"],
+        serde_json::json!([
+            { "rule": "self.replicas <= self.maxReplicas" },
+            { "rule": "self.maxReplicas <= 10", "message": "too many replicas" },
+        ])
+    );
+    assert_eq!(
+        spec["properties"]["storage/class"]["x-kubernetes-validations"],
+        serde_json::json!([{ "rule": "self == oldSelf" }])
+    );
+    assert!(spec["properties"]["replicas"]
+        .get("x-kubernetes-validations")
+        .is_none());
+}
+
+#[test]
+fn root_schema_description() {
+    use kube::core::CustomResourceExt;
+    let description = |crd: k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition| {
+        crd.spec.versions[0]
+            .schema
+            .clone()
+            .unwrap()
+            .open_api_v3_schema
+            .unwrap()
+            .description
+    };
+    assert_eq!(
+        description(Bar::crd()).as_deref(),
+        Some("A bar managed by the bar operator")
+    );
+    assert_eq!(
+        description(Baz::crd()).as_deref(),
+        Some("A baz managed by the baz operator")
+    );
+    assert_eq!(
+        description(Foo::crd()).as_deref(),
+        Some("Auto-generated derived type for FooSpec via `CustomResource`")
+    );
+}
@@ -0,0 +1,11 @@
+use kube_derive::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[kube(group = "clux.dev", version = "v1", kind = "Foo", deprecation_warning = "use v2 instead")]
+struct FooSpec {
+    foo: String,
+}
+
+fn main() {}
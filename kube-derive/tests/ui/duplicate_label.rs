@@ -0,0 +1,17 @@
+use kube_derive::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version = "v1",
+    kind = "Foo",
+    label("app", "foo"),
+    label("app", "bar")
+)]
+struct FooSpec {
+    foo: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use kube_derive::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[kube(group = "clux.dev", version = "v1alpha1", version = "v1", kind = "Foo")]
+struct FooSpec {
+    foo: String,
+}
+
+fn main() {}
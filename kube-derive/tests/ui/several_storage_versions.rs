@@ -0,0 +1,16 @@
+use kube_derive::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[kube(
+    group = "clux.dev",
+    version(name = "v1alpha1", storage),
+    version(name = "v1", storage),
+    kind = "Foo"
+)]
+struct FooSpec {
+    foo: String,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use kube_derive::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[kube(group = "clux.dev", version = "v1", kind = "Foo")]
+struct FooSpec {
+    #[kube(validation = "self.size > 0")]
+    #[serde(flatten)]
+    inner: Inner,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+struct Inner {
+    size: i32,
+}
+
+fn main() {}
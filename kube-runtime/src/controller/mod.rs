@@ -19,9 +19,11 @@ use futures::{
     ready, stream, Future, FutureExt, Stream, StreamExt, TryFuture, TryFutureExt, TryStream, TryStreamExt,
 };
 use kube_client::api::{Api, DynamicObject, ListParams, Resource};
+use parking_lot::Mutex;
 use pin_project::pin_project;
 use serde::de::DeserializeOwned;
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display},
     hash::Hash,
     sync::Arc,
@@ -54,6 +56,8 @@ pub struct Action {
     /// For example, use this to query external systems for updates, expire time-limited resources, or
     /// (in your `error_policy`) retry after errors.
     requeue_after: Option<Duration>,
+    /// The cap for the requeue delay, which doubles with every consecutive failure if set
+    backoff_max: Option<Duration>,
 }
 
 impl Action {
@@ -67,6 +71,22 @@ impl Action {
     pub fn requeue(duration: Duration) -> Self {
         Self {
             requeue_after: Some(duration),
+            backoff_max: None,
+        }
+    }
+
+    /// Action to retry the reconciliation with a capped exponential backoff
+    ///
+    /// The first retry happens after `initial`, and the delay doubles with every consecutive failed reconciliation
+    /// of the same object, up to `max`. The backoff is tracked per object, and reset once it reconciles successfully.
+    ///
+    /// This is intended to be returned from the `error_policy`, so that persistently failing objects
+    /// neither spin hot nor stop being retried.
+    #[must_use]
+    pub fn requeue_with_backoff(initial: Duration, max: Duration) -> Self {
+        Self {
+            requeue_after: Some(initial),
+            backoff_max: Some(max),
         }
     }
 
@@ -80,7 +100,21 @@ impl Action {
     /// frequent changes to the underlying object, or some other hook to retain eventual consistency.
     #[must_use]
     pub fn await_change() -> Self {
-        Self { requeue_after: None }
+        Self {
+            requeue_after: None,
+            backoff_max: None,
+        }
+    }
+
+    /// The delay before requeuing, after `failures` consecutive failed reconciliations of the object
+    fn requeue_delay(&self, failures: u32) -> Option<Duration> {
+        let requeue_after = self.requeue_after?;
+        Some(match self.backoff_max {
+            Some(max) => requeue_after
+                .saturating_mul(1_u32.checked_shl(failures).unwrap_or(u32::MAX))
+                .min(max),
+            None => requeue_after,
+        })
     }
 }
 
@@ -237,6 +271,8 @@ where
     let (scheduler_tx, scheduler_rx) =
         channel::mpsc::channel::<ScheduleRequest<ReconcileRequest<K>>>(APPLIER_REQUEUE_BUF_SIZE);
     let error_policy = Arc::new(error_policy);
+    // Consecutive failures of each object, for `Action::requeue_with_backoff`
+    let failures = Arc::new(Mutex::new(HashMap::<ObjectRef<K>, u32>::new()));
     // Create a stream of ObjectRefs that need to be reconciled
     trystream_try_via(
         // input: stream combining scheduled tasks and user specified inputs event
@@ -263,11 +299,17 @@ where
         move |s| {
             Runner::new(scheduler(s), move |request| {
                 let request = request.clone();
-                match store.get(&request.obj_ref) {
+                let obj = store.get(&request.obj_ref);
+                if obj.is_none() {
+                    // Deleted objects are not retried, so there is no point in remembering their failures
+                    failures.lock().remove(&request.obj_ref);
+                }
+                match obj {
                     Some(obj) => {
                         let scheduler_tx = scheduler_tx.clone();
                         let error_policy_ctx = context.clone();
                         let error_policy = error_policy.clone();
+                        let failures = failures.clone();
                         let reconciler_span = info_span!(
                             "reconciling object",
                             "object.ref" = %request.obj_ref,
@@ -278,10 +320,22 @@ where
                             .into_future()
                             .then(move |res| {
                                 let error_policy = error_policy;
+                                let prior_failures = {
+                                    let mut failures = failures.lock();
+                                    if res.is_ok() {
+                                        failures.remove(&request.obj_ref);
+                                        0
+                                    } else {
+                                        let count = failures.entry(request.obj_ref.clone()).or_default();
+                                        *count = count.saturating_add(1);
+                                        *count - 1
+                                    }
+                                };
                                 RescheduleReconciliation::new(
                                     res,
                                     |err| error_policy(err, error_policy_ctx),
                                     request.obj_ref.clone(),
+                                    prior_failures,
                                     scheduler_tx,
                                 )
                                 // Reconciler errors are OK from the applier's PoV, we need to apply the error policy
@@ -328,6 +382,7 @@ where
         result: Result<Action, ReconcilerErr>,
         error_policy: impl FnOnce(&ReconcilerErr) -> Action,
         obj_ref: ObjectRef<K>,
+        prior_failures: u32,
         reschedule_tx: channel::mpsc::Sender<ScheduleRequest<ReconcileRequest<K>>>,
     ) -> Self {
         let reconciler_finished_at = Instant::now();
@@ -339,13 +394,15 @@ where
 
         Self {
             reschedule_tx,
-            reschedule_request: action.requeue_after.map(|requeue_after| ScheduleRequest {
-                message: ReconcileRequest {
-                    obj_ref,
-                    reason: reschedule_reason,
-                },
-                run_at: reconciler_finished_at + requeue_after,
-            }),
+            reschedule_request: action
+                .requeue_delay(prior_failures)
+                .map(|requeue_after| ScheduleRequest {
+                    message: ReconcileRequest {
+                        obj_ref,
+                        reason: reschedule_reason,
+                    },
+                    run_at: reconciler_finished_at + requeue_after,
+                }),
             result: Some(result),
         }
     }
@@ -835,16 +892,26 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::{convert::Infallible, sync::Arc, time::Duration};
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
 
-    use super::{Action, APPLIER_REQUEUE_BUF_SIZE};
+    use super::{trigger_owners, Action, ReconcileReason, APPLIER_REQUEUE_BUF_SIZE};
     use crate::{
         applier,
         reflector::{self, ObjectRef},
         watcher, Controller,
     };
-    use futures::{pin_mut, StreamExt, TryStreamExt};
-    use k8s_openapi::api::core::v1::ConfigMap;
+    use futures::{pin_mut, stream, StreamExt, TryStreamExt};
+    use k8s_openapi::{
+        api::{apps::v1::Deployment, core::v1::ConfigMap},
+        apimachinery::pkg::apis::meta::v1::OwnerReference,
+    };
     use kube_client::{core::ObjectMeta, Api};
     use tokio::time::timeout;
 
@@ -870,6 +937,112 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn trigger_owners_should_enqueue_matching_owners() {
+        let owner_ref = |api_version: &str, kind: &str, name: &str| OwnerReference {
+            api_version: api_version.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            uid: format!("{name}-uid"),
+            ..OwnerReference::default()
+        };
+        let child = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("child".to_string()),
+                namespace: Some("default".to_string()),
+                owner_references: Some(vec![
+                    owner_ref("apps/v1", "Deployment", "web"),
+                    owner_ref("v1", "Secret", "unrelated"),
+                ]),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        let requests =
+            trigger_owners::<Deployment, _>(stream::iter([Ok::<_, Infallible>(child.clone())]), (), ())
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].obj_ref,
+            ObjectRef::<Deployment>::new("web").within("default")
+        );
+        assert!(matches!(
+            &requests[0].reason,
+            ReconcileReason::RelatedObjectUpdated { obj_ref } if **obj_ref == ObjectRef::from_obj(&child).erase()
+        ));
+    }
+
+    #[test]
+    fn requeue_with_backoff_doubles_up_to_max() {
+        let action = Action::requeue_with_backoff(Duration::from_secs(1), Duration::from_secs(10));
+        let delays = (0..6).map(|failures| action.requeue_delay(failures).unwrap());
+        assert_eq!(delays.map(|d| d.as_secs()).collect::<Vec<_>>(), [
+            1, 2, 4, 8, 10, 10
+        ]);
+        assert_eq!(action.requeue_delay(u32::MAX), Some(Duration::from_secs(10)));
+        assert_eq!(
+            Action::requeue(Duration::from_secs(5)).requeue_delay(3),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(Action::await_change().requeue_delay(3), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn applier_backs_off_failing_objects_until_they_succeed() {
+        #[derive(Debug, thiserror::Error)]
+        #[error("reconcile failed")]
+        struct Failed;
+
+        let (queue_tx, queue_rx) = futures::channel::mpsc::unbounded::<ObjectRef<ConfigMap>>();
+        let (store_rx, mut store_tx) = reflector::store();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let applier = applier(
+            {
+                let attempts = attempts.clone();
+                move |_, _| {
+                    // Fail three times, then succeed once, then fail again
+                    let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move {
+                        if attempt == 3 {
+                            Ok(Action::await_change())
+                        } else {
+                            Err(Failed)
+                        }
+                    })
+                }
+            },
+            |_: &Failed, _| Action::requeue_with_backoff(Duration::from_secs(1), Duration::from_secs(60)),
+            Arc::new(()),
+            store_rx,
+            queue_rx.map(Result::<_, Infallible>::Ok),
+        );
+        pin_mut!(applier);
+        let obj = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("cm".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        store_tx.apply_watcher_event(&watcher::Event::Applied(obj.clone()));
+
+        let mut gaps = Vec::new();
+        for i in 0..6 {
+            // Trigger the initial reconcile, and again after the success since that does not requeue
+            if i == 0 || i == 4 {
+                queue_tx.unbounded_send(ObjectRef::from_obj(&obj)).unwrap();
+            }
+            let started = tokio::time::Instant::now();
+            applier.next().await.unwrap().ok();
+            gaps.push(started.elapsed().as_secs());
+        }
+        // Reconciles after the first are delayed by 1s, 2s and 4s, then the success resets the backoff
+        assert_eq!(gaps, [0, 1, 2, 4, 0, 1]);
+    }
+
     #[tokio::test]
     async fn applier_must_not_deadlock_if_reschedule_buffer_fills() {
         // This tests that `applier` handles reschedule queue backpressure correctly, by trying to flood it with no-op reconciles
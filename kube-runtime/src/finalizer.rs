@@ -97,6 +97,10 @@ impl FinalizerState {
 /// In addition, adding and removing the finalizer itself may fail. In particular, this may be because of
 /// network errors, lacking permissions, or because another `finalizer` was updated in the meantime on the same object.
 ///
+/// The finalizer patches guard against concurrent updates with JSON patch `test` operations, so a conflicting
+/// change makes the patch fail rather than clobbering the other change. Such conflicts resolve themselves once the
+/// object is reconciled again with its latest version, so it is enough to requeue from the controller's `error_policy`.
+///
 /// [`ObjectMeta::finalizers`]: kube_client::api::ObjectMeta#structfield.finalizers
 pub async fn finalizer<K, ReconcileFut>(
     api: &Api<K>,
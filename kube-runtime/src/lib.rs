@@ -22,8 +22,10 @@ k8s_openapi::k8s_if_ge_1_19! {
     pub mod events;
 }
 pub mod finalizer;
+pub mod migrate;
 pub mod reflector;
 pub mod scheduler;
+pub mod status;
 pub mod utils;
 pub mod wait;
 pub mod watcher;
@@ -33,4 +35,4 @@ pub use finalizer::finalizer;
 pub use reflector::reflector;
 pub use scheduler::scheduler;
 pub use utils::WatchStreamExt;
-pub use watcher::watcher;
+pub use watcher::{metadata_watcher, watcher};
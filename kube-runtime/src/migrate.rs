@@ -0,0 +1,272 @@
+//! Rewrites objects so that they are persisted at the current storage version
+use kube_client::{
+    api::{Api, ListParams, PostParams, Request},
+    core::ErrorResponse,
+    Client, Resource, ResourceExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashSet, fmt::Debug};
+use thiserror::Error;
+
+/// Number of objects fetched per list page
+const PAGE_SIZE: u32 = 500;
+/// Number of times an object is refetched after a write conflict before giving up
+const MAX_CONFLICT_RETRIES: usize = 5;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to list objects to migrate: {0}")]
+    ListFailed(#[source] kube_client::Error),
+    #[error("failed to migrate object {name}: {source}")]
+    ReplaceFailed {
+        name: String,
+        #[source]
+        source: kube_client::Error,
+    },
+}
+
+/// Counts of the objects visited by [`migrate_storage_version`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Migration {
+    /// Objects that were rewritten
+    pub migrated: usize,
+    /// Objects that were deleted before they could be rewritten
+    pub skipped: usize,
+}
+
+/// Rewrite every object of an [`Api`] without changing it
+///
+/// Changing the storage version of a `CustomResourceDefinition` only affects objects written after the change.
+/// This replaces each existing object with itself, forcing the apiserver to re-encode it at the new storage version,
+/// after which the old version can be removed from `status.storedVersions`.
+///
+/// Objects are listed in pages, and progress is logged after each page. Each object is written back to its own
+/// namespace, so an [`Api::all`] migrates namespaced objects across all namespaces.
+/// Objects that are modified concurrently are refetched and retried, and objects
+/// that are deleted concurrently are skipped. If the list expires while paginating, then it is restarted,
+/// skipping the objects that were already migrated.
+///
+/// # Errors
+///
+/// Fails if listing fails, or if an object cannot be replaced even after retrying conflicts.
+///
+/// # Usage
+///
+/// ```no_run
+/// use kube::{Api, Client, CustomResource, runtime::migrate::migrate_storage_version};
+/// # #[derive(CustomResource, serde::Deserialize, serde::Serialize, Clone, Debug, schemars::JsonSchema)]
+/// # #[kube(group = "clux.dev", version = "v2", kind = "Foo", namespaced)]
+/// # struct FooSpec {}
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client: kube::Client = todo!();
+/// let foos: Api<Foo> = Api::all(client);
+/// let migration = migrate_storage_version(&foos).await?;
+/// println!("migrated {} foos", migration.migrated);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn migrate_storage_version<K>(api: &Api<K>) -> Result<Migration, Error>
+where
+    K: Resource + Clone + DeserializeOwned + Serialize + Debug,
+    K::DynamicType: Default,
+{
+    migrate_storage_version_with(api, &K::DynamicType::default()).await
+}
+
+/// Rewrite every object of an [`Api`] without changing it, for resources with a custom `DynamicType`
+///
+/// See [`migrate_storage_version`] for details.
+///
+/// # Errors
+///
+/// Fails if listing fails, or if an object cannot be replaced even after retrying conflicts.
+pub async fn migrate_storage_version_with<K>(
+    api: &Api<K>,
+    dyntype: &K::DynamicType,
+) -> Result<Migration, Error>
+where
+    K: Resource + Clone + DeserializeOwned + Serialize + Debug,
+{
+    let client = api.clone().into_client();
+    let mut migration = Migration::default();
+    // Objects that have already been visited, so that they are not counted twice when the list is restarted
+    let mut visited = HashSet::new();
+    let mut lp = ListParams::default().limit(PAGE_SIZE);
+    loop {
+        let page = match api.list(&lp).await {
+            Ok(page) => page,
+            // The continue token has expired, so start over with a fresh list
+            Err(kube_client::Error::Api(ErrorResponse { code: 410, .. })) if lp.continue_token.is_some() => {
+                tracing::debug!("continue token expired during storage version migration, relisting");
+                lp.continue_token = None;
+                continue;
+            }
+            Err(err) => return Err(Error::ListFailed(err)),
+        };
+        for obj in page.items {
+            if !visited.insert(obj.uid()) {
+                continue;
+            }
+            if replace_unchanged(&client, dyntype, obj).await? {
+                migration.migrated += 1;
+            } else {
+                migration.skipped += 1;
+            }
+        }
+        tracing::info!(
+            migrated = migration.migrated,
+            skipped = migration.skipped,
+            "storage version migration in progress"
+        );
+        match page.metadata.continue_ {
+            Some(token) if !token.is_empty() => lp = lp.continue_token(&token),
+            _ => break,
+        }
+    }
+    Ok(migration)
+}
+
+/// Replaces `obj` with itself, returning `false` if it no longer exists
+///
+/// The object is addressed within its own namespace, since the [`Api`] that listed it may span all namespaces.
+async fn replace_unchanged<K>(client: &Client, dyntype: &K::DynamicType, mut obj: K) -> Result<bool, Error>
+where
+    K: Resource + Clone + DeserializeOwned + Serialize + Debug,
+{
+    let name = obj.name_any();
+    let request = Request::new(K::url_path(dyntype, obj.namespace().as_deref()));
+    let mut conflicts = 0;
+    loop {
+        let replaced = match serde_json::to_vec(&obj) {
+            Ok(data) => match request.replace(&name, &PostParams::default(), data) {
+                Ok(req) => client.request::<K>(req).await,
+                Err(err) => Err(kube_client::Error::BuildRequest(err)),
+            },
+            Err(err) => Err(kube_client::Error::SerdeError(err)),
+        };
+        match replaced {
+            Ok(_) => return Ok(true),
+            Err(kube_client::Error::Api(err)) if is_object_not_found(&err, &name) => return Ok(false),
+            Err(kube_client::Error::Api(ErrorResponse { code: 409, .. }))
+                if conflicts < MAX_CONFLICT_RETRIES =>
+            {
+                conflicts += 1;
+                tracing::debug!(%name, conflicts, "conflict while migrating object, refetching");
+                let fetched = match request.get(&name) {
+                    Ok(req) => client.request::<K>(req).await,
+                    Err(err) => Err(kube_client::Error::BuildRequest(err)),
+                };
+                match fetched {
+                    Ok(fresh) => obj = fresh,
+                    Err(kube_client::Error::Api(err)) if is_object_not_found(&err, &name) => {
+                        return Ok(false)
+                    }
+                    Err(source) => return Err(Error::ReplaceFailed { name, source }),
+                }
+            }
+            Err(source) => return Err(Error::ReplaceFailed { name, source }),
+        }
+    }
+}
+
+/// Whether the apiserver reported that the object itself is gone
+///
+/// Other `404`s, such as for an unknown resource path, are not a sign of a concurrent deletion and must not be skipped.
+fn is_object_not_found(err: &ErrorResponse, name: &str) -> bool {
+    err.code == 404 && err.details.as_ref().map_or(false, |details| details.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_storage_version, Migration};
+    use futures::pin_mut;
+    use http::{Request, Response, StatusCode};
+    use hyper::Body;
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube_client::{Api, Client};
+    use serde_json::json;
+    use tower_test::mock;
+
+    fn respond(status: StatusCode, body: &serde_json::Value) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::from(serde_json::to_vec(body).unwrap()))
+            .unwrap()
+    }
+
+    fn config_map(ns: &str, name: &str) -> serde_json::Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": name, "namespace": ns, "uid": format!("{ns}/{name}"), "resourceVersion": "1" }
+        })
+    }
+
+    fn list(items: &[serde_json::Value], continue_: &str) -> serde_json::Value {
+        json!({ "apiVersion": "v1", "kind": "ConfigMapList", "metadata": { "continue": continue_ }, "items": items })
+    }
+
+    #[tokio::test]
+    async fn migrates_within_each_namespace_and_relists_expired_pages() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/configmaps");
+            send.send_response(respond(StatusCode::OK, &list(&[config_map("a", "one")], "page2")));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::PUT);
+            assert_eq!(request.uri().path(), "/api/v1/namespaces/a/configmaps/one");
+            send.send_response(respond(StatusCode::OK, &config_map("a", "one")));
+
+            // The continue token has expired, so the list starts over
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert!(request.uri().query().unwrap().contains("continue=page2"));
+            send.send_response(respond(
+                StatusCode::GONE,
+                &json!({ "status": "Failure", "reason": "Expired", "code": 410 }),
+            ));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert!(!request.uri().query().unwrap().contains("continue"));
+            send.send_response(respond(
+                StatusCode::OK,
+                &list(&[config_map("a", "one"), config_map("b", "two")], ""),
+            ));
+
+            // Deleted in the meantime
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/api/v1/namespaces/b/configmaps/two");
+            send.send_response(respond(
+                StatusCode::NOT_FOUND,
+                &json!({ "status": "Failure", "reason": "NotFound", "code": 404, "details": { "name": "two", "kind": "configmaps" } }),
+            ));
+        });
+
+        let api: Api<ConfigMap> = Api::all(Client::new(mock_service, "default"));
+        let migration = migrate_storage_version(&api).await.unwrap();
+        assert_eq!(migration, Migration {
+            migrated: 1,
+            skipped: 1
+        });
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unexpected_not_found_is_an_error() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(respond(StatusCode::OK, &list(&[config_map("a", "one")], "")));
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(respond(
+                StatusCode::NOT_FOUND,
+                &json!({ "status": "Failure", "reason": "NotFound", "code": 404 }),
+            ));
+        });
+
+        let api: Api<ConfigMap> = Api::all(Client::new(mock_service, "default"));
+        assert!(migrate_storage_version(&api).await.is_err());
+        spawned.await.unwrap();
+    }
+}
@@ -8,7 +8,7 @@ use crate::watcher;
 use futures::{Stream, TryStreamExt};
 use kube_client::Resource;
 use std::hash::Hash;
-pub use store::{store, Store};
+pub use store::{store, Change, Store};
 
 /// Caches objects from `watcher::Event`s to a local `Store`
 ///
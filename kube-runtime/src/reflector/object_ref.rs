@@ -167,6 +167,15 @@ impl<K: Resource> ObjectRef<K> {
     }
 }
 
+impl<K: Resource> From<&K> for ObjectRef<K>
+where
+    K::DynamicType: Default,
+{
+    fn from(obj: &K) -> Self {
+        Self::from_obj(obj)
+    }
+}
+
 impl<K: Resource> From<ObjectRef<K>> for ObjectReference {
     fn from(val: ObjectRef<K>) -> Self {
         let ObjectRef {
@@ -280,4 +289,26 @@ mod tests {
         };
         assert_eq!(hash_value(&minimal), hash_value(&with_extra));
     }
+
+    #[test]
+    fn derived_resources_should_be_usable_as_keys() {
+        use kube::CustomResource;
+        use schemars::JsonSchema;
+        use serde::{Deserialize, Serialize};
+        use std::collections::HashMap;
+
+        #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+        #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+        struct FooSpec {}
+
+        let mut foo = Foo::new("my-foo", FooSpec {});
+        foo.metadata.namespace = Some("my-namespace".into());
+        let foo_ref = ObjectRef::from(&foo);
+        assert_eq!(foo_ref.to_string(), "Foo.v1.clux.dev/my-foo.my-namespace");
+
+        let mut queue = HashMap::new();
+        queue.insert(foo_ref, 1);
+        queue.insert(ObjectRef::<Foo>::new("my-foo").within("my-namespace"), 2);
+        assert_eq!(queue.len(), 1);
+    }
 }
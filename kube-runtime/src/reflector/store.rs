@@ -2,11 +2,53 @@ use super::ObjectRef;
 use crate::watcher;
 use ahash::AHashMap;
 use derivative::Derivative;
-use kube_client::Resource;
-use parking_lot::RwLock;
-use std::{fmt::Debug, hash::Hash, sync::Arc};
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    Stream,
+};
+use kube_client::{Resource, ResourceExt};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::Notify;
 
 type Cache<K> = Arc<RwLock<AHashMap<ObjectRef<K>, Arc<K>>>>;
+type Subscribers<K> = Arc<Mutex<Vec<UnboundedSender<Change<K>>>>>;
+
+/// Whether the [`Writer`] has received its first full list
+#[derive(Debug, Default)]
+struct Readiness {
+    synced: AtomicBool,
+    notify: Notify,
+}
+
+/// A change to the contents of a [`Store`], as observed by [`Store::subscribe`]
+#[derive(Debug)]
+pub enum Change<K> {
+    /// An object was added to the store
+    Added(Arc<K>),
+    /// An object in the store was replaced with a newer version
+    Modified(Arc<K>),
+    /// An object was removed from the store
+    Deleted(Arc<K>),
+}
+
+impl<K> Clone for Change<K> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added(obj) => Self::Added(obj.clone()),
+            Self::Modified(obj) => Self::Modified(obj.clone()),
+            Self::Deleted(obj) => Self::Deleted(obj.clone()),
+        }
+    }
+}
 
 /// A writable Store handle
 ///
@@ -20,6 +62,9 @@ where
 {
     store: Cache<K>,
     dyntype: K::DynamicType,
+    #[derivative(Debug = "ignore")]
+    subscribers: Subscribers<K>,
+    ready: Arc<Readiness>,
 }
 
 impl<K: 'static + Resource + Clone> Writer<K>
@@ -34,6 +79,8 @@ where
         Writer {
             store: Default::default(),
             dyntype,
+            subscribers: Default::default(),
+            ready: Default::default(),
         }
     }
 
@@ -44,21 +91,39 @@ where
     #[must_use]
     pub fn as_reader(&self) -> Store<K> {
         Store {
-            store: self.store.clone(),
+            cache: self.store.clone(),
+            subscribers: self.subscribers.clone(),
+            ready: self.ready.clone(),
         }
     }
 
     /// Applies a single watcher event to the store
+    ///
+    /// The resulting changes are sent to all [`Store::subscribe`]rs.
     pub fn apply_watcher_event(&mut self, event: &watcher::Event<K>) {
+        // Held throughout, so that new subscribers see either all or none of this event's changes
+        let mut subscribers = self.subscribers.lock();
+        let notify = !subscribers.is_empty();
+        let mut changes = Vec::new();
         match event {
             watcher::Event::Applied(obj) => {
                 let key = ObjectRef::from_obj_with(obj, self.dyntype.clone());
                 let obj = Arc::new(obj.clone());
-                self.store.write().insert(key, obj);
+                let old = self.store.write().insert(key, obj.clone());
+                if notify {
+                    changes.push(if old.is_some() {
+                        Change::Modified(obj)
+                    } else {
+                        Change::Added(obj)
+                    });
+                }
             }
             watcher::Event::Deleted(obj) => {
                 let key = ObjectRef::from_obj_with(obj, self.dyntype.clone());
-                self.store.write().remove(&key);
+                let old = self.store.write().remove(&key);
+                if notify && old.is_some() {
+                    changes.push(Change::Deleted(Arc::new(obj.clone())));
+                }
             }
             watcher::Event::Restarted(new_objs) => {
                 let new_objs = new_objs
@@ -70,9 +135,36 @@ where
                         )
                     })
                     .collect::<AHashMap<_, _>>();
-                *self.store.write() = new_objs;
+                let old_objs = std::mem::replace(&mut *self.store.write(), new_objs.clone());
+                if notify {
+                    for (key, obj) in &new_objs {
+                        match old_objs.get(key) {
+                            None => changes.push(Change::Added(obj.clone())),
+                            Some(old) if old.resource_version() != obj.resource_version() => {
+                                changes.push(Change::Modified(obj.clone()));
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                    for (key, old) in old_objs {
+                        if !new_objs.contains_key(&key) {
+                            changes.push(Change::Deleted(old));
+                        }
+                    }
+                }
+                if !self.ready.synced.swap(true, Ordering::AcqRel) {
+                    self.ready.notify.notify_waiters();
+                }
             }
         }
+        if !changes.is_empty() {
+            // Subscribers that have been dropped are forgotten
+            subscribers.retain(|tx| {
+                changes
+                    .iter()
+                    .all(|change| tx.unbounded_send(change.clone()).is_ok())
+            });
+        }
     }
 }
 
@@ -88,7 +180,10 @@ pub struct Store<K: 'static + Resource>
 where
     K::DynamicType: Hash + Eq,
 {
-    store: Cache<K>,
+    cache: Cache<K>,
+    #[derivative(Debug = "ignore")]
+    subscribers: Subscribers<K>,
+    ready: Arc<Readiness>,
 }
 
 impl<K: 'static + Clone + Resource> Store<K>
@@ -106,7 +201,7 @@ where
     /// reasonable `error_policy`.
     #[must_use]
     pub fn get(&self, key: &ObjectRef<K>) -> Option<Arc<K>> {
-        let store = self.store.read();
+        let store = self.cache.read();
         store
             .get(key)
             // Try to erase the namespace and try again, in case the object is cluster-scoped
@@ -124,11 +219,117 @@ where
     /// Return a full snapshot of the current values
     #[must_use]
     pub fn state(&self) -> Vec<Arc<K>> {
-        let s = self.store.read();
+        let s = self.cache.read();
         s.values().cloned().collect()
     }
+
+    /// Write a JSON array of all cached objects to `writer`, ordered by namespace and name
+    ///
+    /// This is intended for debugging what a controller believes exists, e.g. from an admin endpoint.
+    /// The cache is only locked while taking a [`state`](Self::state) snapshot, so this is cheap to call
+    /// while the reflector is running.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an object cannot be serialized or if writing to `writer` fails.
+    pub fn dump<W: std::io::Write>(&self, writer: W) -> serde_json::Result<()>
+    where
+        K: Serialize,
+    {
+        let mut objects = self.state();
+        objects.sort_by(|a, b| {
+            (a.meta().namespace.as_ref(), a.meta().name.as_ref())
+                .cmp(&(b.meta().namespace.as_ref(), b.meta().name.as_ref()))
+        });
+        let objects = objects.iter().map(Arc::as_ref).collect::<Vec<&K>>();
+        serde_json::to_writer(writer, &objects)
+    }
+
+    /// Whether the store has been populated by an initial list
+    ///
+    /// Until then, the store may be empty or hold only some of the objects in the cluster.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.ready.synced.load(Ordering::Acquire)
+    }
+
+    /// Wait until the store has been populated by an initial list
+    ///
+    /// Controllers should wait for this before reconciling, so that they don't act on a partially filled cache.
+    /// This is the equivalent of client-go's `WaitForCacheSync`. Resolves immediately if the store is already
+    /// [`ready`](Self::is_ready), and never resolves if the [`reflector`](crate::reflector()) is not polled.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams}, runtime::{reflector, watcher, WatchStreamExt}, Client};
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// use futures::StreamExt;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let cms: Api<ConfigMap> = Api::default_namespaced(client);
+    ///     let (reader, writer) = reflector::store();
+    ///     let rf = reflector(writer, watcher(cms, ListParams::default()));
+    ///     tokio::spawn(rf.touched_objects().for_each(|_| futures::future::ready(())));
+    ///     reader.wait_until_ready().await;
+    ///     println!("Found {} configmaps", reader.state().len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_until_ready(&self) {
+        loop {
+            // Registered before checking, so that a notification in between is not missed
+            let notified = self.ready.notify.notified();
+            if self.is_ready() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Subscribe to changes made to the store
+    ///
+    /// Every [`Change`] made by the [`Writer`] after subscribing is sent to the returned stream,
+    /// so that consumers can react to changes immediately rather than polling [`state`](Self::state).
+    /// If `snapshot` is set, then the objects already in the store are sent first as [`Change::Added`],
+    /// without any changes being missed or duplicated in between.
+    ///
+    /// Relists are reported as the difference between the old and new contents of the store.
+    ///
+    /// The stream is unbounded, so it should be consumed promptly. Dropping it unsubscribes.
+    pub fn subscribe(&self, snapshot: bool) -> impl Stream<Item = Change<K>> {
+        let (tx, rx) = mpsc::unbounded();
+        let mut subscribers = self.subscribers.lock();
+        if snapshot {
+            for obj in self.cache.read().values() {
+                let _ = tx.unbounded_send(Change::Added(obj.clone()));
+            }
+        }
+        subscribers.push(tx);
+        rx
+    }
 }
 
+impl<K: 'static + Clone + Resource> Store<K>
+where
+    K::DynamicType: Eq + Hash + Clone + Default,
+{
+    /// Retrieve a `clone()` of the cluster-scoped object called `name`, if it is in the cache.
+    ///
+    /// This is a shorthand for [`Store::get`] with an [`ObjectRef::new`], and has the same staleness caveats.
+    #[must_use]
+    pub fn get_named(&self, name: &str) -> Option<Arc<K>> {
+        self.get(&ObjectRef::new(name))
+    }
+
+    /// Retrieve a `clone()` of the object called `name` in `namespace`, if it is in the cache.
+    ///
+    /// Like `kubectl get -n <namespace> <name>`, the namespace comes first.
+    /// This is a shorthand for [`Store::get`] with an [`ObjectRef::within`], and has the same staleness caveats.
+    #[must_use]
+    pub fn get_within(&self, namespace: &str, name: &str) -> Option<Arc<K>> {
+        self.get(&ObjectRef::new(name).within(namespace))
+    }
+}
 
 /// Create a (Reader, Writer) for a `Store<K>` for a typed resource `K`
 ///
@@ -148,7 +349,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{store, Writer};
+    use super::{store, Change, Writer};
     use crate::{reflector::ObjectRef, watcher};
     use k8s_openapi::api::core::v1::ConfigMap;
     use kube_client::api::ObjectMeta;
@@ -169,6 +370,24 @@ mod tests {
         assert_eq!(store.get(&ObjectRef::from_obj(&cm)).as_deref(), Some(&cm));
     }
 
+    #[test]
+    fn should_allow_getting_objects_by_name() {
+        let cm = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("obj".to_string()),
+                namespace: Some("ns".to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        let mut store_w = Writer::default();
+        store_w.apply_watcher_event(&watcher::Event::Applied(cm.clone()));
+        let store = store_w.as_reader();
+        assert_eq!(store.get_within("ns", "obj").as_deref(), Some(&cm));
+        assert_eq!(store.get_within("other", "obj"), None);
+        assert_eq!(store.get_named("obj"), None);
+    }
+
     #[test]
     fn should_not_allow_getting_namespaced_object_by_clusterscoped_ref() {
         let cm = ConfigMap {
@@ -219,4 +438,130 @@ mod tests {
         let store = store_w.as_reader();
         assert_eq!(store.get(&ObjectRef::from_obj(&nsed_cm)).as_deref(), Some(&cm));
     }
+
+    #[test]
+    fn dump_should_write_sorted_json() {
+        let cm = |ns: &str, name: &str| ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(ns.to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        let (store, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Restarted(vec![
+            cm("b", "obj"),
+            cm("a", "obj2"),
+            cm("a", "obj1"),
+        ]));
+        let mut buf = Vec::new();
+        store.dump(&mut buf).unwrap();
+        let dumped: Vec<ConfigMap> = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(dumped, vec![cm("a", "obj1"), cm("a", "obj2"), cm("b", "obj")]);
+    }
+
+    #[test]
+    fn should_track_dynamic_objects() {
+        use kube_client::{
+            api::{ApiResource, DynamicObject, GroupVersionKind},
+            ResourceExt,
+        };
+
+        let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("clux.dev", "v1", "Foo"));
+        let mut foo = DynamicObject::new("foo", &ar).within("ns");
+        foo.metadata.resource_version = Some("1".into());
+        let mut store_w = Writer::new(ar.clone());
+        store_w.apply_watcher_event(&watcher::Event::Applied(foo.clone()));
+        let store = store_w.as_reader();
+
+        let foo_ref = ObjectRef::from_obj_with(&foo, ar.clone());
+        assert_eq!(
+            store.get(&foo_ref).unwrap().resource_version().as_deref(),
+            Some("1")
+        );
+
+        foo.metadata.resource_version = Some("2".into());
+        store_w.apply_watcher_event(&watcher::Event::Applied(foo.clone()));
+        assert_eq!(
+            store.get(&foo_ref).unwrap().resource_version().as_deref(),
+            Some("2")
+        );
+
+        store_w.apply_watcher_event(&watcher::Event::Deleted(foo));
+        assert!(store.get(&ObjectRef::new_with("foo", ar).within("ns")).is_none());
+    }
+
+    #[test]
+    fn subscribers_should_receive_changes() {
+        use futures::StreamExt;
+
+        let cm = |name: &str, version: &str| ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("ns".to_string()),
+                resource_version: Some(version.to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        let describe = |change: Change<ConfigMap>| {
+            let (verb, obj) = match change {
+                Change::Added(obj) => ("added", obj),
+                Change::Modified(obj) => ("modified", obj),
+                Change::Deleted(obj) => ("deleted", obj),
+            };
+            let name = obj.metadata.name.as_deref().unwrap();
+            let version = obj.metadata.resource_version.as_deref().unwrap();
+            format!("{verb} {name}@{version}")
+        };
+        let (reader, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Applied(cm("a", "1")));
+        let late = reader.subscribe(true);
+        let live = reader.subscribe(false);
+
+        writer.apply_watcher_event(&watcher::Event::Applied(cm("a", "2")));
+        writer.apply_watcher_event(&watcher::Event::Applied(cm("b", "3")));
+        writer.apply_watcher_event(&watcher::Event::Restarted(vec![cm("b", "3"), cm("c", "4")]));
+        writer.apply_watcher_event(&watcher::Event::Deleted(cm("c", "5")));
+        drop((reader, writer));
+
+        let live = futures::executor::block_on(live.map(describe).collect::<Vec<_>>());
+        assert_eq!(live, vec![
+            "modified a@2",
+            "added b@3",
+            "added c@4",
+            "deleted a@2",
+            "deleted c@5"
+        ]);
+        let late = futures::executor::block_on(late.map(describe).collect::<Vec<_>>());
+        assert_eq!(late[0], "added a@1");
+        assert_eq!(late[1..], live[..]);
+    }
+
+    #[tokio::test]
+    async fn should_become_ready_after_initial_list() {
+        use futures::{poll, FutureExt};
+
+        let (reader, mut writer) = store::<ConfigMap>();
+        let ready = reader.wait_until_ready();
+        futures::pin_mut!(ready);
+        assert!(poll!(ready.as_mut()).is_pending());
+
+        let cm = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("obj".to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        writer.apply_watcher_event(&watcher::Event::Applied(cm));
+        assert!(!reader.is_ready());
+        assert!(poll!(ready.as_mut()).is_pending());
+
+        writer.apply_watcher_event(&watcher::Event::Restarted(vec![]));
+        assert!(reader.is_ready());
+        ready.await;
+        assert!(reader.clone().wait_until_ready().now_or_never().is_some());
+    }
 }
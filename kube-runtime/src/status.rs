@@ -0,0 +1,341 @@
+//! Debounced status writes for controllers that update status frequently
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use kube_client::{
+    api::{Api, Patch, PatchParams},
+    Resource,
+};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+/// The outcome of a call to [`StatusDebouncer::write`]
+#[derive(Debug)]
+pub enum StatusWrite<K> {
+    /// The latest status within the window was patched onto the object
+    Written(K),
+    /// The status was queued and will be written by an earlier caller once its window elapses
+    Coalesced,
+    /// The status was semantically identical to the last written status, so no request was made
+    Unchanged,
+}
+
+/// How long the last written status of an object is remembered after its last write attempt
+const SLOT_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Default)]
+struct Slot {
+    /// The status most recently accepted by the apiserver
+    last_written: Option<Value>,
+    /// The newest status waiting to be written
+    pending: Option<Value>,
+    /// Whether a caller is currently waiting out the window to write `pending`, or writing it
+    scheduled: bool,
+    /// When a write to this object last finished
+    updated_at: Option<Instant>,
+}
+
+/// What a writer should do after submitting a status to a [`Slot`]
+#[derive(Debug, PartialEq, Eq)]
+enum Submit {
+    Skip,
+    Coalesce,
+    Schedule,
+}
+
+impl Slot {
+    fn submit(&mut self, status: Value) -> Submit {
+        if self.scheduled {
+            self.pending = Some(status);
+            Submit::Coalesce
+        } else if self.last_written.as_ref() == Some(&status) {
+            self.pending = None;
+            Submit::Skip
+        } else {
+            self.pending = Some(status);
+            self.scheduled = true;
+            Submit::Schedule
+        }
+    }
+
+    /// Takes the pending status for writing, unless it matches what was last written
+    ///
+    /// The slot stays scheduled while the taken status is being written, so that concurrent
+    /// submissions are coalesced rather than racing it.
+    fn take(&mut self) -> Option<Value> {
+        match self.pending.take() {
+            Some(status) if self.last_written.as_ref() != Some(&status) => Some(status),
+            _ => {
+                self.scheduled = false;
+                None
+            }
+        }
+    }
+
+    fn written(&mut self, status: Value) {
+        self.last_written = Some(status);
+        self.updated_at = Some(Instant::now());
+    }
+
+    /// Puts back a status that could not be written, unless a newer one was submitted meanwhile
+    fn failed(&mut self, status: Value) {
+        self.pending.get_or_insert(status);
+        self.scheduled = false;
+        self.updated_at = Some(Instant::now());
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.scheduled && self.updated_at.map_or(true, |at| at.elapsed() >= SLOT_TTL)
+    }
+}
+
+/// Coalesces status writes to objects of a single [`Api`]
+///
+/// Every call to [`write`](StatusDebouncer::write) records the desired status of an object.
+/// The first call for an object waits out the debounce window and then writes the newest status
+/// recorded during that window, while calls made during the window return
+/// [`StatusWrite::Coalesced`] immediately. Statuses that serialize identically to the last
+/// status written are skipped entirely.
+///
+/// The final status submitted is always written: submissions made while a write is in flight
+/// are written once it completes, and a write that fails is not recorded, so the next
+/// submission for that object is retried rather than skipped. Objects are forgotten once
+/// they have not been written for five minutes.
+///
+/// ```no_run
+/// use kube::{Api, Client, runtime::status::StatusDebouncer};
+/// use k8s_openapi::api::apps::v1::Deployment;
+/// use serde_json::json;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let deploys: Api<Deployment> = Api::namespaced(client, "default");
+///     let debouncer = StatusDebouncer::new(deploys).window(Duration::from_millis(200));
+///     debouncer.write("blog", &json!({ "observedGeneration": 2 })).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct StatusDebouncer<K> {
+    api: Api<K>,
+    window: Duration,
+    params: PatchParams,
+    slots: Arc<Mutex<HashMap<String, Slot>>>,
+}
+
+impl<K> StatusDebouncer<K>
+where
+    K: Resource + Clone + DeserializeOwned + Debug,
+{
+    /// Create a debouncer writing through `api` with a default window of 500ms
+    #[must_use]
+    pub fn new(api: Api<K>) -> Self {
+        Self {
+            api,
+            window: Duration::from_millis(500),
+            params: PatchParams::default(),
+            slots: Arc::default(),
+        }
+    }
+
+    /// Set the window within which writes to the same object are coalesced
+    #[must_use]
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the [`PatchParams`] used for the status merge patches
+    #[must_use]
+    pub fn params(mut self, params: PatchParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Submit the desired `status` of the object called `name`
+    ///
+    /// # Errors
+    ///
+    /// Fails if `status` cannot be serialized or if the status patch is rejected.
+    pub async fn write<S: Serialize>(&self, name: &str, status: &S) -> kube_client::Result<StatusWrite<K>> {
+        let status = serde_json::to_value(status).map_err(kube_client::Error::SerdeError)?;
+        match self
+            .slots
+            .lock()
+            .entry(name.to_string())
+            .or_default()
+            .submit(status)
+        {
+            Submit::Skip => return Ok(StatusWrite::Unchanged),
+            Submit::Coalesce => return Ok(StatusWrite::Coalesced),
+            Submit::Schedule => {}
+        }
+        let mut written = None;
+        loop {
+            tokio::time::sleep(self.window).await;
+            let status = {
+                let mut slots = self.slots.lock();
+                let status = slots.get_mut(name).and_then(Slot::take);
+                if status.is_none() {
+                    slots.retain(|_, slot| !slot.is_idle());
+                }
+                status
+            };
+            let status = if let Some(status) = status {
+                status
+            } else {
+                return Ok(written.map_or(StatusWrite::Unchanged, StatusWrite::Written));
+            };
+            let patch = Patch::Merge(json!({ "status": status }));
+            match self.api.patch_status(name, &self.params, &patch).await {
+                Ok(obj) => {
+                    self.slot(name).written(status);
+                    written = Some(obj);
+                }
+                Err(err) => {
+                    self.slot(name).failed(status);
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn slot(&self, name: &str) -> parking_lot::MappedMutexGuard<'_, Slot> {
+        parking_lot::MutexGuard::map(self.slots.lock(), |slots| {
+            slots.entry(name.to_string()).or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Slot, StatusDebouncer, StatusWrite, Submit};
+    use futures::pin_mut;
+    use http::{Request, Response, StatusCode};
+    use hyper::Body;
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube_client::{Api, Client};
+    use serde_json::json;
+    use std::time::Duration;
+    use tower_test::mock;
+
+    fn respond(status: StatusCode, body: &serde_json::Value) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::from(serde_json::to_vec(body).unwrap()))
+            .unwrap()
+    }
+
+    async fn patched_status(request: Request<Body>) -> serde_json::Value {
+        assert_eq!(
+            request.uri().path(),
+            "/api/v1/namespaces/default/configmaps/blog/status"
+        );
+        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()["status"].clone()
+    }
+
+    fn config_map() -> serde_json::Value {
+        json!({ "apiVersion": "v1", "kind": "ConfigMap", "metadata": { "name": "blog", "namespace": "default" } })
+    }
+
+    fn debouncer() -> (
+        StatusDebouncer<ConfigMap>,
+        mock::Handle<Request<Body>, Response<Body>>,
+    ) {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let api = Api::namespaced(Client::new(mock_service, "default"), "default");
+        (
+            StatusDebouncer::new(api).window(Duration::from_millis(10)),
+            handle,
+        )
+    }
+
+    #[test]
+    fn slot_coalesces_within_window_and_skips_identical() {
+        let mut slot = Slot::default();
+        assert_eq!(slot.submit(json!({"ready": false})), Submit::Schedule);
+        assert_eq!(slot.submit(json!({"ready": true})), Submit::Coalesce);
+        let latest = slot.take().unwrap();
+        assert_eq!(latest, json!({"ready": true}));
+        // Still scheduled while the write is in flight
+        assert_eq!(slot.submit(json!({"ready": true})), Submit::Coalesce);
+        slot.written(latest);
+        assert_eq!(slot.take(), None);
+
+        assert_eq!(slot.submit(json!({"ready": true})), Submit::Skip);
+        assert_eq!(slot.submit(json!({"ready": false})), Submit::Schedule);
+        // Flipping back to the written state within the window makes the write redundant
+        assert_eq!(slot.submit(json!({"ready": true})), Submit::Coalesce);
+        assert_eq!(slot.take(), None);
+        assert!(!slot.scheduled);
+        // Recently written slots are kept around to skip identical statuses
+        assert!(!slot.is_idle());
+        assert!(Slot::default().is_idle());
+    }
+
+    #[tokio::test]
+    async fn write_coalesces_submissions_made_while_in_flight() {
+        let (debouncer, handle) = debouncer();
+        let writer = debouncer.clone();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(patched_status(request).await, json!({"ready": false}));
+            // Submitted while the first write is in flight, so it must not race it
+            assert!(matches!(
+                writer.write("blog", &json!({"ready": true})).await.unwrap(),
+                StatusWrite::Coalesced
+            ));
+            send.send_response(respond(StatusCode::OK, &config_map()));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(patched_status(request).await, json!({"ready": true}));
+            send.send_response(respond(StatusCode::OK, &config_map()));
+        });
+        assert!(matches!(
+            debouncer.write("blog", &json!({"ready": false})).await.unwrap(),
+            StatusWrite::Written(_)
+        ));
+        spawned.await.unwrap();
+        assert!(matches!(
+            debouncer.write("blog", &json!({"ready": true})).await.unwrap(),
+            StatusWrite::Unchanged
+        ));
+    }
+
+    #[tokio::test]
+    async fn write_retries_status_after_failure() {
+        let (debouncer, handle) = debouncer();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(patched_status(request).await, json!({"ready": true}));
+            send.send_response(respond(
+                StatusCode::CONFLICT,
+                &json!({ "status": "Failure", "message": "conflict", "reason": "Conflict", "code": 409 }),
+            ));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(patched_status(request).await, json!({"ready": true}));
+            send.send_response(respond(StatusCode::OK, &config_map()));
+        });
+        assert!(debouncer.write("blog", &json!({"ready": true})).await.is_err());
+        assert_eq!(
+            debouncer.slots.lock()["blog"].pending,
+            Some(json!({"ready": true}))
+        );
+        assert!(matches!(
+            debouncer.write("blog", &json!({"ready": true})).await.unwrap(),
+            StatusWrite::Written(_)
+        ));
+        spawned.await.unwrap();
+    }
+}
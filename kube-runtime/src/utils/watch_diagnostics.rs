@@ -0,0 +1,194 @@
+use crate::watcher::{Error, Event};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures::{ready, Stream};
+use k8s_openapi::chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use pin_project::pin_project;
+use std::{collections::VecDeque, sync::Arc};
+
+/// An error observed on a [`watcher`](crate::watcher()) stream
+#[derive(Clone, Debug)]
+pub struct RecordedError {
+    /// When the error was observed
+    pub timestamp: DateTime<Utc>,
+    /// The rendered error message
+    pub message: String,
+}
+
+#[derive(Debug)]
+struct State {
+    capacity: usize,
+    applied: u64,
+    deleted: u64,
+    restarts: u64,
+    errors: u64,
+    recent_errors: VecDeque<RecordedError>,
+    listed: bool,
+}
+
+/// Diagnostics recorded from a [`watcher`](crate::watcher()) stream
+///
+/// Attach it to a stream with [`WatchStreamExt::record_diagnostics`](super::WatchStreamExt::record_diagnostics),
+/// and inspect it from elsewhere (e.g. an admin endpoint) to find out why a watcher keeps reconnecting,
+/// or export the counters as metrics.
+/// Clones share the same underlying state.
+#[derive(Clone, Debug)]
+pub struct WatchDiagnostics {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for WatchDiagnostics {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl WatchDiagnostics {
+    /// Create diagnostics retaining the `capacity` most recent errors
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                capacity,
+                applied: 0,
+                deleted: 0,
+                restarts: 0,
+                errors: 0,
+                recent_errors: VecDeque::with_capacity(capacity),
+                listed: false,
+            })),
+        }
+    }
+
+    /// The number of [`Event::Applied`] events observed
+    #[must_use]
+    pub fn applied(&self) -> u64 {
+        self.state.lock().applied
+    }
+
+    /// The number of [`Event::Deleted`] events observed
+    #[must_use]
+    pub fn deleted(&self) -> u64 {
+        self.state.lock().deleted
+    }
+
+    /// The number of times the watcher has relisted after its initial list
+    #[must_use]
+    pub fn restarts(&self) -> u64 {
+        self.state.lock().restarts
+    }
+
+    /// The total number of errors observed
+    #[must_use]
+    pub fn errors(&self) -> u64 {
+        self.state.lock().errors
+    }
+
+    /// The most recent errors observed, oldest first
+    #[must_use]
+    pub fn recent_errors(&self) -> Vec<RecordedError> {
+        self.state.lock().recent_errors.iter().cloned().collect()
+    }
+
+    fn record_applied(&self) {
+        self.state.lock().applied += 1;
+    }
+
+    fn record_deleted(&self) {
+        self.state.lock().deleted += 1;
+    }
+
+    fn record_restart(&self) {
+        let mut state = self.state.lock();
+        if state.listed {
+            state.restarts += 1;
+        }
+        state.listed = true;
+    }
+
+    fn record_error(&self, err: &Error) {
+        let mut state = self.state.lock();
+        state.errors += 1;
+        if state.capacity == 0 {
+            return;
+        }
+        if state.recent_errors.len() == state.capacity {
+            state.recent_errors.pop_front();
+        }
+        state.recent_errors.push_back(RecordedError {
+            timestamp: Utc::now(),
+            message: err.to_string(),
+        });
+    }
+}
+
+#[pin_project]
+/// Stream returned by the [`record_diagnostics`](super::WatchStreamExt::record_diagnostics) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct RecordDiagnostics<St> {
+    #[pin]
+    stream: St,
+    diagnostics: WatchDiagnostics,
+}
+
+impl<St> RecordDiagnostics<St> {
+    pub(super) fn new(stream: St, diagnostics: WatchDiagnostics) -> Self {
+        Self { stream, diagnostics }
+    }
+}
+
+impl<St, K> Stream for RecordDiagnostics<St>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let item = ready!(me.stream.poll_next(cx));
+        match &item {
+            Some(Ok(Event::Applied(_))) => me.diagnostics.record_applied(),
+            Some(Ok(Event::Deleted(_))) => me.diagnostics.record_deleted(),
+            Some(Ok(Event::Restarted(_))) => me.diagnostics.record_restart(),
+            Some(Err(err)) => me.diagnostics.record_error(err),
+            None => {}
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Error, Event, RecordDiagnostics, WatchDiagnostics};
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn records_events_restarts_and_bounded_errors() {
+        let data = stream::iter([
+            Ok(Event::Restarted(vec![0])),
+            Err(Error::TooManyObjects),
+            Ok(Event::Applied(1)),
+            Err(Error::TooManyObjects),
+            Ok(Event::Restarted(vec![0, 1])),
+            Ok(Event::Deleted(0)),
+            Err(Error::TooManyObjects),
+            Ok(Event::Restarted(vec![1])),
+        ]);
+        let diagnostics = WatchDiagnostics::new(2);
+        let events = RecordDiagnostics::new(data, diagnostics.clone())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len(), 8);
+        assert_eq!(diagnostics.applied(), 1);
+        assert_eq!(diagnostics.deleted(), 1);
+        assert_eq!(diagnostics.restarts(), 2);
+        assert_eq!(diagnostics.errors(), 3);
+        let recent = diagnostics.recent_errors();
+        assert_eq!(recent.len(), 2);
+        assert!(recent[0].timestamp <= recent[1].timestamp);
+        assert_eq!(recent[1].message, "too many objects matched search criteria");
+    }
+}
@@ -1,5 +1,9 @@
 use crate::{
-    utils::{event_flatten::EventFlatten, stream_backoff::StreamBackoff},
+    utils::{
+        event_flatten::EventFlatten,
+        stream_backoff::StreamBackoff,
+        watch_diagnostics::{RecordDiagnostics, WatchDiagnostics},
+    },
     watcher,
 };
 use backoff::backoff::Backoff;
@@ -36,5 +40,15 @@ pub trait WatchStreamExt: Stream {
     {
         EventFlatten::new(self, true)
     }
+
+    /// Record events, restarts and errors of a [`watcher()`] stream into [`WatchDiagnostics`]
+    ///
+    /// All events and errors are passed through unchanged.
+    fn record_diagnostics<K>(self, diagnostics: &WatchDiagnostics) -> RecordDiagnostics<Self>
+    where
+        Self: Stream<Item = Result<watcher::Event<K>, watcher::Error>> + Sized,
+    {
+        RecordDiagnostics::new(self, diagnostics.clone())
+    }
 }
 impl<St: ?Sized> WatchStreamExt for St where St: Stream {}
@@ -271,6 +271,71 @@ pub mod conditions {
     }
 }
 
+/// Utilities for installing `CustomResourceDefinition`s
+pub mod crd {
+    use super::{await_condition, conditions};
+    use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+    use kube_client::{
+        api::{Patch, PatchParams},
+        Api,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    pub enum Error {
+        #[error("CustomResourceDefinition has no name")]
+        NoName,
+        #[error("failed to apply CustomResourceDefinition: {0}")]
+        Apply(#[source] kube_client::Error),
+        #[error("failed to wait for CustomResourceDefinition to be established: {0}")]
+        Await(#[source] super::Error),
+    }
+
+    /// Server-side apply a `CustomResourceDefinition`, and wait for it to be established
+    ///
+    /// Objects of a newly created custom resource cannot be created until the apiserver has established it,
+    /// so applying the CRD and then immediately creating objects is racy. This waits for the
+    /// [`is_crd_established`](conditions::is_crd_established) condition before returning the established CRD.
+    ///
+    /// Like [`await_condition`], this does not time out by itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the CRD could not be applied, or if the wait was interrupted.
+    ///
+    /// # Usage
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, PatchParams}, Client, CustomResource, CustomResourceExt, runtime::wait::crd::apply_and_establish};
+    /// # #[derive(CustomResource, serde::Deserialize, serde::Serialize, Clone, Debug, schemars::JsonSchema)]
+    /// # #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced)]
+    /// # struct FooSpec {}
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: kube::Client = todo!();
+    /// let crds = Api::all(client);
+    /// let pp = PatchParams::apply("my-operator").force();
+    /// apply_and_establish(crds, &Foo::crd(), &pp).await?;
+    /// // Foo objects can now be created
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_and_establish(
+        api: Api<CustomResourceDefinition>,
+        crd: &CustomResourceDefinition,
+        pp: &PatchParams,
+    ) -> Result<CustomResourceDefinition, Error> {
+        let name = crd.metadata.name.clone().ok_or(Error::NoName)?;
+        let applied = api
+            .patch(&name, pp, &Patch::Apply(crd))
+            .await
+            .map_err(Error::Apply)?;
+        let established = await_condition(api, &name, conditions::is_crd_established())
+            .await
+            .map_err(Error::Await)?;
+        Ok(established.unwrap_or(applied))
+    }
+}
+
 /// Utilities for deleting objects
 pub mod delete {
     use super::{await_condition, conditions};
@@ -5,29 +5,50 @@
 use crate::utils::ResetTimerBackoff;
 use backoff::{backoff::Backoff, ExponentialBackoff};
 use derivative::Derivative;
-use futures::{stream::BoxStream, Stream, StreamExt};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt};
 use kube_client::{
-    api::{ListParams, Resource, ResourceExt, WatchEvent},
+    api::{ListParams, ObjectList, PartialObjectMeta, Resource, ResourceExt, WatchEvent},
     Api,
 };
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
-use std::{clone::Clone, fmt::Debug, time::Duration};
+use std::{clone::Clone, collections::HashMap, fmt::Debug, time::Duration};
 use thiserror::Error;
+use tokio::time::Instant;
 
 #[derive(Debug, Error)]
 pub enum Error {
+    /// Listing the objects failed, before any watch was opened
     #[error("failed to perform initial object list: {0}")]
     InitialListFailed(#[source] kube_client::Error),
+    /// Opening the watch connection failed
     #[error("failed to start watching object: {0}")]
     WatchStartFailed(#[source] kube_client::Error),
+    /// The apiserver sent an error on an open watch
     #[error("error returned by apiserver during watch: {0}")]
     WatchError(#[source] kube_client::error::ErrorResponse),
+    /// An open watch connection broke
     #[error("watch stream failed: {0}")]
     WatchFailed(#[source] kube_client::Error),
     #[error("too many objects matched search criteria")]
     TooManyObjects,
 }
+
+impl Error {
+    /// Whether the apiserver rejected the list or watch with `403 Forbidden`
+    ///
+    /// Missing RBAC permissions will not resolve themselves by retrying,
+    /// so callers may want to give up on the stream rather than back off forever.
+    #[must_use]
+    pub fn is_forbidden(&self) -> bool {
+        match self {
+            Error::InitialListFailed(kube_client::Error::Api(err))
+            | Error::WatchStartFailed(kube_client::Error::Api(err))
+            | Error::WatchError(err) => err.code == 403,
+            _ => false,
+        }
+    }
+}
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[derive(Debug, Clone)]
@@ -113,6 +134,18 @@ impl<K> Event<K> {
 enum State<K: Resource + Clone> {
     /// The Watcher is empty, and the next [`poll`](Stream::poll_next) will start the initial LIST to get all existing objects
     Empty,
+    /// The next [`poll`](Stream::poll_next) will LIST a single object, only to find the current resource version to watch from
+    Latest,
+    /// The initial state is being streamed through a watch, see [`ListParams::send_initial_events`]
+    ///
+    /// Objects are buffered until the bookmark marking the end of the initial events,
+    /// after which they are emitted as a single [`Event::Restarted`].
+    InitialWatch {
+        /// The objects received so far, keyed by uid
+        objects: HashMap<Option<String>, K>,
+        #[derivative(Debug = "ignore")]
+        stream: WatchEventStream<K>,
+    },
     /// The initial LIST was successful, so we should move on to starting the actual watch.
     InitListed { resource_version: String },
     /// The watch is in progress, from this point we just return events from the server.
@@ -124,36 +157,161 @@ enum State<K: Resource + Clone> {
     Watching {
         resource_version: String,
         #[derivative(Debug = "ignore")]
-        stream: BoxStream<'static, kube_client::Result<WatchEvent<K>>>,
+        stream: WatchEventStream<K>,
     },
 }
 
+type WatchEventStream<K> = BoxStream<'static, kube_client::Result<WatchEvent<K>>>;
+
+/// The annotation set on the bookmark that ends the initial events of a streaming list
+const INITIAL_EVENTS_END_ANNOTATION: &str = "k8s.io/initial-events-end";
+
+/// Used to control whether the watcher receives the full object, or only the
+/// metadata
+trait ApiMode {
+    type Value: Resource + Clone + Send + 'static;
+
+    fn list<'a>(&'a self, lp: &'a ListParams) -> BoxFuture<'a, kube_client::Result<ObjectList<Self::Value>>>;
+
+    fn watch<'a>(
+        &'a self,
+        lp: &'a ListParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchEventStream<Self::Value>>>;
+}
+
+/// A wrapper around the `Api` of a `Resource` type that when used by the
+/// watcher will return the entire (full) object
+struct FullObject<'a, K> {
+    api: &'a Api<K>,
+}
+
+impl<K> ApiMode for FullObject<'_, K>
+where
+    K: Resource + Clone + DeserializeOwned + Debug + Send + 'static,
+{
+    type Value = K;
+
+    fn list<'a>(&'a self, lp: &'a ListParams) -> BoxFuture<'a, kube_client::Result<ObjectList<K>>> {
+        self.api.list(lp).boxed()
+    }
+
+    fn watch<'a>(
+        &'a self,
+        lp: &'a ListParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchEventStream<K>>> {
+        async move { Ok(self.api.watch(lp, version).await?.boxed()) }.boxed()
+    }
+}
+
+/// A wrapper around the `Api` of a `Resource` type that when used by the
+/// watcher will return only the metadata associated with an object
+struct MetaOnly<'a, K> {
+    api: &'a Api<K>,
+}
+
+impl<K> ApiMode for MetaOnly<'_, K>
+where
+    K: Resource + Clone + DeserializeOwned + Debug + Send + 'static,
+{
+    type Value = PartialObjectMeta;
+
+    fn list<'a>(
+        &'a self,
+        lp: &'a ListParams,
+    ) -> BoxFuture<'a, kube_client::Result<ObjectList<PartialObjectMeta>>> {
+        self.api.list_metadata(lp).boxed()
+    }
+
+    fn watch<'a>(
+        &'a self,
+        lp: &'a ListParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchEventStream<PartialObjectMeta>>> {
+        async move { Ok(self.api.watch_metadata(lp, version).await?.boxed()) }.boxed()
+    }
+}
+
+/// The `ListParams` for watching (and finding the latest version), without the options of the initial list
+fn watch_params(list_params: &ListParams) -> ListParams {
+    ListParams {
+        send_initial_events: false,
+        resource_version: None,
+        resource_version_match: None,
+        ..list_params.clone()
+    }
+}
+
 /// Progresses the watcher a single step, returning (event, state)
 ///
 /// This function should be trampolined: if event == `None`
 /// then the function should be called again until it returns a Some.
-async fn step_trampolined<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
-    api: &Api<K>,
-    list_params: &ListParams,
-    state: State<K>,
-) -> (Option<Result<Event<K>>>, State<K>) {
+async fn step_trampolined<A>(
+    api: &A,
+    list_params: &mut ListParams,
+    state: State<A::Value>,
+    resync_at: Option<Instant>,
+) -> (Option<Result<Event<A::Value>>>, State<A::Value>)
+where
+    A: ApiMode,
+{
     match state {
+        // A streaming list is a watch, which starts from the version that was requested for the list
+        State::Empty if list_params.send_initial_events => match api
+            .watch(
+                &ListParams {
+                    send_initial_events: true,
+                    ..watch_params(list_params)
+                },
+                list_params.resource_version.as_deref().unwrap_or_default(),
+            )
+            .await
+        {
+            Ok(stream) => (None, State::InitialWatch {
+                objects: HashMap::new(),
+                stream,
+            }),
+            // Apiservers without streaming lists reject `resourceVersionMatch` on watches
+            Err(kube_client::Error::Api(err)) if err.code == 400 || err.code == 422 => {
+                tracing::debug!(
+                    message = %err.message,
+                    "streaming lists are not supported by the apiserver, falling back to listing"
+                );
+                list_params.send_initial_events = false;
+                (None, State::Empty)
+            }
+            Err(err) => (Some(Err(Error::InitialListFailed(err))), State::Empty),
+        },
         State::Empty => match api.list(list_params).await {
             Ok(list) => (Some(Ok(Event::Restarted(list.items))), State::InitListed {
                 resource_version: list.metadata.resource_version.unwrap(),
             }),
             Err(err) => (Some(Err(err).map_err(Error::InitialListFailed)), State::Empty),
         },
-        State::InitListed { resource_version } => match api.watch(list_params, &resource_version).await {
-            Ok(stream) => (None, State::Watching {
-                resource_version,
-                stream: stream.boxed(),
+        State::Latest => match api.list(&watch_params(list_params).limit(1)).await {
+            Ok(list) => (None, State::InitListed {
+                resource_version: list.metadata.resource_version.unwrap(),
             }),
-            Err(err) => (
-                Some(Err(err).map_err(Error::WatchStartFailed)),
-                State::InitListed { resource_version },
-            ),
+            Err(err) => (Some(Err(Error::InitialListFailed(err))), State::Latest),
         },
+        State::InitialWatch { objects, stream } => step_initial_watch(objects, stream).await,
+        State::InitListed { resource_version } => {
+            match api.watch(&watch_params(list_params), &resource_version).await {
+                Ok(stream) => (None, State::Watching {
+                    resource_version,
+                    // End the watch when a resync is due, so that it falls through to a relist
+                    stream: match resync_at {
+                        Some(deadline) => stream.take_until(tokio::time::sleep_until(deadline)).boxed(),
+                        None => stream,
+                    },
+                }),
+                Err(err) => (
+                    Some(Err(err).map_err(Error::WatchStartFailed)),
+                    State::InitListed { resource_version },
+                ),
+            }
+        }
         State::Watching {
             resource_version,
             mut stream,
@@ -192,19 +350,62 @@ async fn step_trampolined<K: Resource + Clone + DeserializeOwned + Debug + Send
                 resource_version,
                 stream,
             }),
+            None if resync_at.map_or(false, |deadline| Instant::now() >= deadline) => (None, State::Empty),
             None => (None, State::InitListed { resource_version }),
         },
     }
 }
 
+/// Collects the initial events of a streaming list, until the apiserver marks their end with a bookmark
+async fn step_initial_watch<K: Resource + Clone>(
+    mut objects: HashMap<Option<String>, K>,
+    mut stream: WatchEventStream<K>,
+) -> (Option<Result<Event<K>>>, State<K>) {
+    match stream.next().await {
+        Some(Ok(WatchEvent::Added(obj) | WatchEvent::Modified(obj))) => {
+            objects.insert(obj.uid(), obj);
+            (None, State::InitialWatch { objects, stream })
+        }
+        Some(Ok(WatchEvent::Deleted(obj))) => {
+            objects.remove(&obj.uid());
+            (None, State::InitialWatch { objects, stream })
+        }
+        Some(Ok(WatchEvent::Bookmark(bm)))
+            if bm
+                .metadata
+                .annotations
+                .get(INITIAL_EVENTS_END_ANNOTATION)
+                .map(String::as_str)
+                == Some("true") =>
+        {
+            (
+                Some(Ok(Event::Restarted(objects.into_values().collect()))),
+                State::Watching {
+                    resource_version: bm.metadata.resource_version,
+                    stream,
+                },
+            )
+        }
+        Some(Ok(WatchEvent::Bookmark(_))) => (None, State::InitialWatch { objects, stream }),
+        Some(Ok(WatchEvent::Error(err))) => (Some(Err(Error::WatchError(err))), State::Empty),
+        Some(Err(err)) => (Some(Err(Error::WatchFailed(err))), State::Empty),
+        // The watch timed out before the initial events were done, so start over
+        None => (None, State::Empty),
+    }
+}
+
 /// Trampoline helper for `step_trampolined`
-async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
-    api: &Api<K>,
-    list_params: &ListParams,
-    mut state: State<K>,
-) -> (Result<Event<K>>, State<K>) {
+async fn step<A>(
+    api: &A,
+    list_params: &mut ListParams,
+    mut state: State<A::Value>,
+    resync_at: Option<Instant>,
+) -> (Result<Event<A::Value>>, State<A::Value>)
+where
+    A: ApiMode,
+{
     loop {
-        match step_trampolined(api, list_params, state).await {
+        match step_trampolined(api, list_params, state, resync_at).await {
             (Some(result), new_state) => return (result, new_state),
             (None, new_state) => state = new_state,
         }
@@ -255,19 +456,215 @@ async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
 /// This will normally happen immediately, but you can use [`StreamBackoff`](crate::utils::StreamBackoff)
 /// to introduce an artificial delay. [`default_backoff`] returns a suitable default set of parameters.
 ///
+/// Errors that will not go away by retrying, such as missing RBAC permissions, are still retried forever.
+/// Check [`Error::is_forbidden`] to stop watching instead.
+///
 /// If the watch connection is interrupted, then `watcher` will attempt to restart the watch using the last
 /// [resource version](https://kubernetes.io/docs/reference/using-api/api-concepts/#efficient-detection-of-changes)
 /// that we have seen on the stream. If this is successful then the stream is simply resumed from where it left off.
 /// If this fails because the resource version is no longer valid then we start over with a new stream, starting with
 /// an [`Event::Restarted`]. The internals mechanics of recovery should be considered an implementation detail.
+///
+/// # Streaming lists
+///
+/// With [`ListParams::send_initial_events`], the initial state is streamed through the watch itself rather than
+/// fetched by a separate LIST call, which avoids the apiserver building one large response for big collections.
+/// The streamed objects are still collected into a single [`Event::Restarted`], which is emitted once the apiserver
+/// marks the end of the initial events with a bookmark.
+///
+/// Streaming lists require Kubernetes 1.27 with the `WatchList` feature gate. If the apiserver rejects the
+/// streaming watch as invalid, then `watcher` falls back to listing for the rest of its lifetime.
 pub fn watcher<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
     api: Api<K>,
     list_params: ListParams,
 ) -> impl Stream<Item = Result<Event<K>>> + Send {
     futures::stream::unfold(
         (api, list_params, State::Empty),
-        |(api, list_params, state)| async {
-            let (event, state) = step(&api, &list_params, state).await;
+        |(api, mut list_params, state)| async {
+            let (event, state) = step(&FullObject { api: &api }, &mut list_params, state, None).await;
+            Some((event, (api, list_params, state)))
+        },
+    )
+}
+
+/// Watches a Kubernetes Resource for changes continuously, starting from a known `resource_version`
+///
+/// This works like [`watcher`], but skips the initial LIST and instead watches forward from `resource_version`,
+/// which is typically taken from a list that the caller has already performed. This supports the
+/// list-then-watch pattern without a gap between the two, and without listing every object twice on startup.
+///
+/// No [`Event::Restarted`] is emitted for the starting state, so a [`reflector`] store must be seeded
+/// from the caller's own list first. If `resource_version` is too old for the apiserver to watch from,
+/// then the watcher falls back to a full relist, emitting an [`Event::Restarted`] as [`watcher`] does.
+///
+/// ```no_run
+/// use kube::{
+///   api::{Api, ListParams}, Client,
+///   runtime::{reflector, watcher::{self, watcher_from, Event}, WatchStreamExt}
+/// };
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::TryStreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+///     let lp = ListParams::default();
+///
+///     let list = pods.list(&lp).await?;
+///     let resource_version = list.metadata.resource_version.clone().unwrap_or_default();
+///     let (reader, mut writer) = reflector::store();
+///     writer.apply_watcher_event(&Event::Restarted(list.items));
+///
+///     reflector(writer, watcher_from(pods, lp, resource_version))
+///         .applied_objects()
+///         .try_for_each(|_| async { Ok(()) })
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+/// [`reflector`]: super::reflector::reflector
+pub fn watcher_from<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
+    api: Api<K>,
+    list_params: ListParams,
+    resource_version: String,
+) -> impl Stream<Item = Result<Event<K>>> + Send {
+    futures::stream::unfold(
+        (api, list_params, State::InitListed { resource_version }),
+        |(api, mut list_params, state)| async {
+            let (event, state) = step(&FullObject { api: &api }, &mut list_params, state, None).await;
+            Some((event, (api, list_params, state)))
+        },
+    )
+}
+
+/// Watches a Kubernetes Resource for changes made from now on
+///
+/// This works like [`watcher_from`], starting from the current resource version of the collection,
+/// so that only changes made after the watch starts are emitted. The current resource version is found
+/// by listing a single object, rather than every object.
+///
+/// This differs from watching with a resource version of `"0"` or none at all: the apiserver starts both of those
+/// watches by sending a synthetic `Added` event for every existing object, replaying the full state
+/// (`"0"` from its cache, which may be stale, and none from the most recent state).
+///
+/// No [`Event::Restarted`] is emitted on startup, so a [`reflector`] store is never populated with objects
+/// that do not change. If the watch falls out of the apiserver's watch window, then the watcher falls back
+/// to a full relist, emitting an [`Event::Restarted`] as [`watcher`] does.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, Client, runtime::{watcher::watcher_from_latest, WatchStreamExt}};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::TryStreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+///     watcher_from_latest(pods, ListParams::default())
+///         .applied_objects()
+///         .try_for_each(|p| async move {
+///             println!("Changed: {:?}", p.metadata.name);
+///             Ok(())
+///         })
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+/// [`reflector`]: super::reflector::reflector
+pub fn watcher_from_latest<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
+    api: Api<K>,
+    list_params: ListParams,
+) -> impl Stream<Item = Result<Event<K>>> + Send {
+    futures::stream::unfold(
+        (api, list_params, State::Latest),
+        |(api, mut list_params, state)| async {
+            let (event, state) = step(&FullObject { api: &api }, &mut list_params, state, None).await;
+            Some((event, (api, list_params, state)))
+        },
+    )
+}
+
+/// Watches a Kubernetes Resource for changes continuously, relisting every `resync_period`
+///
+/// This works like [`watcher`], but additionally forces a full relist (emitting an [`Event::Restarted`])
+/// once `resync_period` has passed since the previous list. This catches any changes (such as deletions)
+/// that may have been missed by the watch, similar to the `resyncPeriod` of client-go's informers.
+///
+/// Relists caused by the watch falling out of the apiserver's watch window also reset the period,
+/// so the objects are never relisted twice in quick succession.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, Client, runtime::{watcher::watcher_with_resync, WatchStreamExt}};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::TryStreamExt;
+/// use std::time::Duration;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+///     watcher_with_resync(pods, ListParams::default(), Duration::from_secs(300))
+///         .applied_objects()
+///         .try_for_each(|_| async { Ok(()) })
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub fn watcher_with_resync<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
+    api: Api<K>,
+    list_params: ListParams,
+    resync_period: Duration,
+) -> impl Stream<Item = Result<Event<K>>> + Send {
+    futures::stream::unfold(
+        (api, list_params, State::Empty, None),
+        move |(api, mut list_params, state, resync_at)| async move {
+            let (event, state) = step(&FullObject { api: &api }, &mut list_params, state, resync_at).await;
+            let resync_at = match &event {
+                Ok(Event::Restarted(_)) => Some(Instant::now() + resync_period),
+                _ => resync_at,
+            };
+            Some((event, (api, list_params, state, resync_at)))
+        },
+    )
+}
+
+/// Watches the metadata of a Kubernetes Resource for changes continuously
+///
+/// This works exactly like [`watcher`], and tracks resource versions and recovers from errors
+/// in the same way, but only the metadata of each object is transferred from the apiserver.
+/// The returned events contain [`PartialObjectMeta`] rather than the full objects.
+///
+/// This is useful for watching large objects (such as `ConfigMap` or `Secret` objects with big
+/// payloads) when only existence or metadata changes (like labels or annotations) are of interest,
+/// as it saves both bandwidth and memory.
+///
+/// ```no_run
+/// use kube::{
+///   api::{Api, ListParams, ResourceExt}, Client,
+///   runtime::{watcher, metadata_watcher, WatchStreamExt}
+/// };
+/// use k8s_openapi::api::core::v1::ConfigMap;
+/// use futures::{StreamExt, TryStreamExt};
+/// #[tokio::main]
+/// async fn main() -> Result<(), watcher::Error> {
+///     let client = Client::try_default().await.unwrap();
+///     let cms: Api<ConfigMap> = Api::namespaced(client, "apps");
+///
+///     metadata_watcher(cms, ListParams::default()).applied_objects()
+///         .try_for_each(|cm| async move {
+///          println!("Applied: {}", cm.name_any());
+///             Ok(())
+///         })
+///         .await?;
+///    Ok(())
+/// }
+/// ```
+pub fn metadata_watcher<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
+    api: Api<K>,
+    list_params: ListParams,
+) -> impl Stream<Item = Result<Event<PartialObjectMeta>>> + Send {
+    futures::stream::unfold(
+        (api, list_params, State::Empty),
+        |(api, mut list_params, state)| async {
+            let (event, state) = step(&MetaOnly { api: &api }, &mut list_params, state, None).await;
             Some((event, (api, list_params, state)))
         },
     )
@@ -318,3 +715,197 @@ pub fn default_backoff() -> impl Backoff + Send + Sync {
     };
     ResetTimerBackoff::new(expo, Duration::from_secs(120))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{step, ApiMode, Error, Event, State, WatchEventStream};
+    use futures::{future::BoxFuture, FutureExt, StreamExt};
+    use k8s_openapi::{api::core::v1::ConfigMap, apimachinery::pkg::apis::meta::v1::ListMeta};
+    use kube_client::{
+        api::{ListParams, ObjectList, ResourceExt, WatchEvent},
+        error::ErrorResponse,
+    };
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn api_error(code: u16) -> ErrorResponse {
+        ErrorResponse {
+            status: "Failure".into(),
+            message: String::new(),
+            reason: String::new(),
+            code,
+            details: None,
+        }
+    }
+
+    #[test]
+    fn forbidden_errors_are_detected() {
+        assert!(Error::InitialListFailed(kube_client::Error::Api(api_error(403))).is_forbidden());
+        assert!(Error::WatchStartFailed(kube_client::Error::Api(api_error(403))).is_forbidden());
+        assert!(Error::WatchError(api_error(403)).is_forbidden());
+        assert!(!Error::WatchStartFailed(kube_client::Error::Api(api_error(500))).is_forbidden());
+        assert!(!Error::WatchError(api_error(410)).is_forbidden());
+        assert!(!Error::TooManyObjects.is_forbidden());
+    }
+
+    /// Serves a single list and a single prepared watch, for driving the state machine
+    struct StreamingApi {
+        events: Mutex<Vec<WatchEvent<ConfigMap>>>,
+        supports_streaming: bool,
+    }
+
+    impl ApiMode for StreamingApi {
+        type Value = ConfigMap;
+
+        fn list<'a>(
+            &'a self,
+            lp: &'a ListParams,
+        ) -> BoxFuture<'a, kube_client::Result<ObjectList<ConfigMap>>> {
+            assert!(!lp.send_initial_events);
+            let list = ObjectList {
+                metadata: ListMeta {
+                    resource_version: Some("1".into()),
+                    ..ListMeta::default()
+                },
+                items: vec![config_map("listed", "1")],
+            };
+            async move { Ok(list) }.boxed()
+        }
+
+        fn watch<'a>(
+            &'a self,
+            lp: &'a ListParams,
+            version: &'a str,
+        ) -> BoxFuture<'a, kube_client::Result<WatchEventStream<ConfigMap>>> {
+            // Reject the parameters that a real watch request rejects
+            let request = kube_client::core::Request::new("/api/v1/configmaps").watch(lp, version);
+            let result = if let Err(err) = request {
+                Err(kube_client::Error::BuildRequest(err))
+            } else if lp.send_initial_events && !self.supports_streaming {
+                Err(kube_client::Error::Api(api_error(422)))
+            } else {
+                let events = std::mem::take(&mut *self.events.lock().unwrap());
+                Ok(futures::stream::iter(events.into_iter().map(Ok)).boxed())
+            };
+            async move { result }.boxed()
+        }
+    }
+
+    fn config_map(name: &str, resource_version: &str) -> ConfigMap {
+        serde_json::from_value(json!({
+            "metadata": { "name": name, "uid": name, "resourceVersion": resource_version }
+        }))
+        .unwrap()
+    }
+
+    fn bookmark(resource_version: &str, initial_events_end: bool) -> WatchEvent<ConfigMap> {
+        let annotations = if initial_events_end {
+            json!({ "k8s.io/initial-events-end": "true" })
+        } else {
+            json!({})
+        };
+        serde_json::from_value(json!({
+            "type": "BOOKMARK",
+            "object": {
+                "apiVersion": "v1",
+                "kind": "ConfigMap",
+                "metadata": { "resourceVersion": resource_version, "annotations": annotations }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn streaming_list_emits_restarted_after_initial_events() {
+        let api = StreamingApi {
+            events: Mutex::new(vec![
+                WatchEvent::Added(config_map("a", "2")),
+                WatchEvent::Added(config_map("b", "3")),
+                WatchEvent::Deleted(config_map("a", "4")),
+                bookmark("4", false),
+                bookmark("5", true),
+                WatchEvent::Modified(config_map("b", "6")),
+            ]),
+            supports_streaming: true,
+        };
+        let mut lp = ListParams::default().send_initial_events(true);
+
+        let (event, state) = step(&api, &mut lp, State::Empty, None).await;
+        match event.unwrap() {
+            Event::Restarted(objs) => {
+                assert_eq!(objs.iter().map(ResourceExt::name_any).collect::<Vec<_>>(), ["b"]);
+            }
+            event => panic!("expected a restart, got {event:?}"),
+        }
+        assert!(matches!(&state, State::Watching { resource_version, .. } if resource_version == "5"));
+
+        let (event, _) = step(&api, &mut lp, state, None).await;
+        assert!(
+            matches!(event.unwrap(), Event::Applied(obj) if obj.resource_version().as_deref() == Some("6"))
+        );
+        assert!(lp.send_initial_events);
+    }
+
+    #[tokio::test]
+    async fn streaming_list_falls_back_to_listing() {
+        let api = StreamingApi {
+            events: Mutex::new(Vec::new()),
+            supports_streaming: false,
+        };
+        let mut lp = ListParams::default().send_initial_events(true);
+
+        let (event, state) = step(&api, &mut lp, State::Empty, None).await;
+        assert!(matches!(event.unwrap(), Event::Restarted(objs) if objs.len() == 1));
+        assert!(matches!(state, State::InitListed { .. }));
+        assert!(!lp.send_initial_events);
+    }
+
+    #[tokio::test]
+    async fn latest_lists_without_initial_events() {
+        let api = StreamingApi {
+            events: Mutex::new(vec![WatchEvent::Added(config_map("a", "2"))]),
+            supports_streaming: true,
+        };
+        let mut lp = ListParams::default().send_initial_events(true);
+
+        let (event, state) = step(&api, &mut lp, State::Latest, None).await;
+        assert!(matches!(event.unwrap(), Event::Applied(obj) if obj.name_any() == "a"));
+        assert!(matches!(state, State::Watching { resource_version, .. } if resource_version == "2"));
+    }
+
+    #[tokio::test]
+    async fn watches_after_a_list_at_a_resource_version() {
+        let api = StreamingApi {
+            events: Mutex::new(vec![WatchEvent::Added(config_map("a", "2"))]),
+            supports_streaming: false,
+        };
+        let mut lp = ListParams::default().at("0");
+
+        let (event, state) = step(&api, &mut lp, State::Empty, None).await;
+        assert!(matches!(event.unwrap(), Event::Restarted(objs) if objs.len() == 1));
+        let (event, state) = step(&api, &mut lp, state, None).await;
+        assert!(matches!(event.unwrap(), Event::Applied(obj) if obj.name_any() == "a"));
+        assert!(matches!(state, State::Watching { resource_version, .. } if resource_version == "2"));
+
+        let mut lp = ListParams::default().at("0");
+        api.events
+            .lock()
+            .unwrap()
+            .push(WatchEvent::Added(config_map("b", "3")));
+        let (event, _) = step(&api, &mut lp, State::Latest, None).await;
+        assert!(matches!(event.unwrap(), Event::Applied(obj) if obj.name_any() == "b"));
+    }
+
+    #[tokio::test]
+    async fn streaming_list_starts_at_the_resource_version() {
+        let api = StreamingApi {
+            events: Mutex::new(vec![WatchEvent::Added(config_map("a", "2")), bookmark("2", true)]),
+            supports_streaming: true,
+        };
+        let mut lp = ListParams::default().send_initial_events(true).at("0");
+
+        let (event, state) = step(&api, &mut lp, State::Empty, None).await;
+        assert!(matches!(event.unwrap(), Event::Restarted(objs) if objs.len() == 1));
+        assert!(matches!(state, State::Watching { resource_version, .. } if resource_version == "2"));
+    }
+}
@@ -519,4 +519,37 @@ mod test {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    #[ignore] // needs cluster (dry-run creates and patches a cm)
+    async fn dry_run_does_not_persist() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::api::PostParams;
+        let client = Client::try_default().await?;
+        let api = Api::<ConfigMap>::default_namespaced(client);
+        let name = "kube-dry-run-test";
+        let mut cm = ConfigMap::default();
+        cm.metadata.name = Some(name.into());
+
+        let created = api
+            .create(
+                &PostParams {
+                    dry_run: true,
+                    ..Default::default()
+                },
+                &cm,
+            )
+            .await?;
+        assert_eq!(created.name_any(), name);
+        assert_eq!(api.get_opt(name).await?, None);
+
+        let patch = Patch::Apply(serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "data": { "key": "value" },
+        }));
+        let pp = PatchParams::apply("kube").dry_run();
+        api.patch(name, &pp, &patch).await?;
+        assert_eq!(api.get_opt(name).await?, None);
+        Ok(())
+    }
 }